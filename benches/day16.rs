@@ -0,0 +1,133 @@
+//! Compares visited-set implementations on a plain breadth-first beam search of day 16
+//!
+//! Run with `cargo bench --bench day16`.
+use aoc_2023::{Co2, Dir, FastHashSet, Grid, GridBitSet};
+use std::{collections::HashSet, hint::black_box, time::Instant};
+
+const INPUT: &str = include_str!("../examples/inputs/day16.txt");
+const ROUNDS: usize = 5;
+
+fn out_dirs(tile: char, dir: Dir) -> Vec<Dir> {
+    use Dir as D;
+    match (tile, dir) {
+        ('/', D::North) | ('\\', D::South) => vec![D::East],
+        ('/', D::East) | ('\\', D::West) => vec![D::North],
+        ('/', D::South) | ('\\', D::North) => vec![D::West],
+        ('/', D::West) | ('\\', D::East) => vec![D::South],
+        ('|', d) if d.is_horizontal() => vec![D::North, D::South],
+        ('-', d) if d.is_vertical() => vec![D::West, D::East],
+        _ => vec![dir],
+    }
+}
+
+/// Energizes the grid from `start`, tracking visited beam states and energized cells through
+/// the given set operations
+fn energize(
+    grid: &Grid<char>,
+    start: (Co2<usize>, Dir),
+    mut visit: impl FnMut(Co2<usize>, Dir) -> bool,
+    mut energize: impl FnMut(Co2<usize>),
+) {
+    let mut stack = vec![start];
+    while let Some((co, dir)) = stack.pop() {
+        if !visit(co, dir) {
+            continue;
+        }
+        energize(co);
+        for out in out_dirs(grid[co], dir) {
+            if let Some(next) = grid.step(co, out) {
+                stack.push((next, out));
+            }
+        }
+    }
+}
+
+fn edge_starts(grid: &Grid<char>) -> Vec<(Co2<usize>, Dir)> {
+    let (rows, cols) = (grid.rows(), grid.cols());
+    (0..rows)
+        .flat_map(|row| [(Co2(row, 0), Dir::East), (Co2(row, cols - 1), Dir::West)])
+        .chain(
+            (0..cols).flat_map(|col| [(Co2(0, col), Dir::South), (Co2(rows - 1, col), Dir::North)]),
+        )
+        .collect()
+}
+
+fn bench(name: &str, f: impl Fn() -> usize) -> usize {
+    let mut best = f64::MAX;
+    let mut result = 0;
+    for _ in 0..ROUNDS {
+        let t = Instant::now();
+        result = black_box(f());
+        best = best.min(t.elapsed().as_secs_f64());
+    }
+    println!("{name:<16} {:>8.2} ms", best * 1e3);
+    result
+}
+
+fn main() {
+    let grid = Grid::parse(INPUT);
+    let starts = edge_starts(&grid);
+
+    let std = bench("std HashSet", || {
+        starts
+            .iter()
+            .map(|&start| {
+                let mut visited = HashSet::new();
+                let mut energized = HashSet::new();
+                energize(
+                    &grid,
+                    start,
+                    |co, dir| visited.insert((co, dir)),
+                    |co| {
+                        energized.insert(co);
+                    },
+                );
+                energized.len()
+            })
+            .max()
+            .unwrap()
+    });
+
+    let fast = bench("FastHashSet", || {
+        starts
+            .iter()
+            .map(|&start| {
+                let mut visited = FastHashSet::default();
+                let mut energized = FastHashSet::default();
+                energize(
+                    &grid,
+                    start,
+                    |co, dir| visited.insert((co, dir)),
+                    |co| {
+                        energized.insert(co);
+                    },
+                );
+                energized.len()
+            })
+            .max()
+            .unwrap()
+    });
+
+    let bits = bench("GridBitSet", || {
+        starts
+            .iter()
+            .map(|&start| {
+                // One layer of columns per direction
+                let mut visited = GridBitSet::new(grid.rows(), grid.cols() * 4);
+                let mut energized = GridBitSet::for_grid(&grid);
+                energize(
+                    &grid,
+                    start,
+                    |co, dir| visited.insert(Co2(co[0], co[1] * 4 + dir.index())),
+                    |co| {
+                        energized.insert(co);
+                    },
+                );
+                energized.count()
+            })
+            .max()
+            .unwrap()
+    });
+
+    assert_eq!((std, fast, bits), (8221, 8221, 8221));
+}
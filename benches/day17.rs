@@ -0,0 +1,90 @@
+//! Compares a binary heap with the bucket queue of `min_path_cost` on day 17
+//!
+//! Run with `cargo bench --bench day17`.
+use aoc_2023::{
+    crucible::{min_path_cost, RunRules},
+    Co2, Dir, Grid,
+};
+use std::{cmp::Reverse, collections::BinaryHeap, hint::black_box, time::Instant};
+
+const INPUT: &str = include_str!("../examples/inputs/day17.txt");
+const ROUNDS: usize = 5;
+
+/// Dijkstra's algorithm over the same states as `min_path_cost`, settled with a binary heap
+fn heap_path_cost(weights: &Grid<u8>, goal: Co2<usize>, rules: RunRules) -> Option<u32> {
+    let runs = rules.max_run + 1;
+    let state = |co: Co2<usize>, dir: Dir, run: usize| {
+        (weights.index_of(co) * 4 + dir.index()) * runs + run
+    };
+    let mut dist = vec![u32::MAX; weights.len() * 4 * runs];
+    let mut heap = BinaryHeap::new();
+
+    let relax = |heap: &mut BinaryHeap<_>, dist: &mut [u32], co, dir, run, d: u32| {
+        if let Some(nco) = weights.step(co, dir) {
+            let nd = d + weights[nco] as u32;
+            let ns = state(nco, dir, run);
+            if nd < dist[ns] {
+                dist[ns] = nd;
+                heap.push(Reverse((nd, ns)));
+            }
+        }
+    };
+    for dir in Dir::ALL {
+        relax(&mut heap, &mut dist, Co2(0, 0), dir, 1, 0);
+    }
+
+    while let Some(Reverse((d, s))) = heap.pop() {
+        // Skip entries that were improved on after being queued
+        if d != dist[s] {
+            continue;
+        }
+        let run = s % runs;
+        let dir = Dir::ALL[s / runs % 4];
+        let co = weights.co_of(s / runs / 4);
+        if co == goal && run >= rules.min_run {
+            return Some(d);
+        }
+        if run < rules.max_run {
+            relax(&mut heap, &mut dist, co, dir, run + 1, d);
+        }
+        if run >= rules.min_run {
+            for ndir in [dir.turn_left(), dir.turn_right()] {
+                relax(&mut heap, &mut dist, co, ndir, 1, d);
+            }
+        }
+    }
+    None
+}
+
+fn bench(name: &str, f: impl Fn() -> u32) -> u32 {
+    let mut best = f64::MAX;
+    let mut result = 0;
+    for _ in 0..ROUNDS {
+        let t = Instant::now();
+        result = black_box(f());
+        best = best.min(t.elapsed().as_secs_f64());
+    }
+    println!("{name:<16} {:>8.2} ms", best * 1e3);
+    result
+}
+
+fn main() {
+    let weights = Grid::parse(INPUT).map(|c| c.to_digit(10).unwrap() as u8);
+    let goal = Co2(weights.rows() - 1, weights.cols() - 1);
+    let both = || [RunRules::crucible(), RunRules::ultra_crucible()];
+
+    let heap = bench("BinaryHeap", || {
+        both()
+            .map(|rules| heap_path_cost(&weights, goal, rules).unwrap())
+            .iter()
+            .sum()
+    });
+    let buckets = bench("bucket queue", || {
+        both()
+            .map(|rules| min_path_cost(&weights, Co2(0, 0), goal, rules).unwrap())
+            .iter()
+            .sum()
+    });
+
+    assert_eq!((heap, buckets), (694 + 829, 694 + 829));
+}
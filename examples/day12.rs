@@ -1,7 +1,8 @@
+use aoc_2023::Memo;
 use itertools::Itertools;
-use std::{fmt, iter};
+use std::fmt;
 
-const _TEST_INPUT: &str = include_str!("inputs/day12_test.txt");
+const TEST_INPUT: &str = include_str!("inputs/day12_test.txt");
 const INPUT: &str = include_str!("inputs/day12.txt");
 
 #[derive(PartialEq, Clone)]
@@ -78,46 +79,37 @@ fn to_config(v: &[Status]) -> Vec<usize> {
 }
 */
 
-fn permutations<'s>(springs: &'s [Status]) -> Box<dyn Iterator<Item = Vec<usize>> + 's> {
-    // If the solution contains a '.', the solutions is always cartesian_product(perm(left), perm(right))
-    if let Some(dot_pos) = springs.iter().position(|s| s == &Status::Inactive) {
-        let left = &springs[0..dot_pos];
-        let right = &springs[dot_pos + 1..];
-
-        let pleft = permutations(left).collect_vec();
-        let pright = permutations(right).collect_vec();
-
-        let solutions = pleft
-            .into_iter()
-            .cartesian_product(pright)
-            .map(|(left, right)| left.into_iter().chain(right).collect());
-
-        Box::new(solutions)
-    }
-    // If the solution contains any '?', the solution is all permutations where ? is swapped for either '.' or '#'
-    else if springs.contains(&Status::Unknown) {
-        let unknown_indices = springs
-            .iter()
-            .enumerate()
-            .filter_map(|(idx, status)| (status == &Status::Unknown).then_some(idx));
-        let solutions = unknown_indices.flat_map(|idx| {
-            let mut resolved_active = springs.to_vec();
-            resolved_active[idx] = Status::Active;
-            let pactive = permutations(&resolved_active).collect_vec();
-
-            let mut resolved_inactive = springs.to_vec();
-            resolved_inactive[idx] = Status::Inactive;
-            let pinactive = permutations(&resolved_inactive).collect_vec();
+/// Counts the ways to place the damaged spring `groups` onto `springs[pos..]`, with `springs`
+/// fixed for the lifetime of `memo`
+fn arrangements(
+    springs: &[Status],
+    groups: &[usize],
+    (pos, group): (usize, usize),
+    memo: &mut Memo<(usize, usize), u64>,
+) -> u64 {
+    memo.get_or_compute((pos, group), |memo, &(pos, group)| {
+        let Some(status) = springs.get(pos) else {
+            return (group == groups.len()) as u64;
+        };
 
-            pactive.into_iter().chain(pinactive)
-        });
+        // Leave this spring operational
+        let mut count = 0;
+        if status != &Status::Active {
+            count += arrangements(springs, groups, (pos + 1, group), memo);
+        }
 
-        Box::new(solutions)
-    }
-    // If the solution is only "###" then the solution is len("###")
-    else {
-        Box::new(iter::once(vec![springs.len()]))
-    }
+        // Start the next damaged group here, followed by an operational spring or the end
+        if let Some(&len) = groups.get(group) {
+            let end = pos + len;
+            let fits = end <= springs.len()
+                && !springs[pos..end].contains(&Status::Inactive)
+                && springs.get(end) != Some(&Status::Active);
+            if status != &Status::Inactive && fits {
+                count += arrangements(springs, groups, (end + 1, group + 1), memo);
+            }
+        }
+        count
+    })
 }
 
 fn part1(input: &str) -> i64 {
@@ -133,23 +125,21 @@ fn part1(input: &str) -> i64 {
         (springs, config)
     });
 
+    let mut memo = Memo::new();
     let counts = rows.map(|(springs, config)| {
-        let count = permutations(&springs).filter(|p| p == &config).count();
-        println!("{springs:?} -> {count}");
-        count
+        memo.clear();
+        arrangements(&springs, &config, (0, 0), &mut memo)
     });
 
-    counts.sum::<usize>() as i64
+    counts.sum::<u64>() as i64
 }
 
 fn main() -> anyhow::Result<()> {
-    let p1_test = part1(INPUT);
+    let p1_test = part1(TEST_INPUT);
     assert_eq!(p1_test, 21);
 
-    /*
     let p1 = part1(INPUT);
     println!("Part 1: {}", p1);
-    */
     Ok(())
 }
 
@@ -1,25 +1,17 @@
+use aoc_2023::{holiday_hash as hash, HolidayHash, HolidayKey};
 use regex::Regex;
+use std::hash::BuildHasher;
 
 const INPUT: &str = include_str!("inputs/day15.txt");
 const _TEST_INPUT: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
 
-fn hash(s: &str) -> u8 {
-    let mut cur: u8 = 0;
-    for c in s.chars() {
-        let ascii = c as u8;
-        cur = cur.wrapping_add(ascii);
-        cur = cur.wrapping_mul(17);
-        // cur %= 256;
-    }
-    cur
-}
-
 fn to_instr<'s>(s: &'s str, re: &Regex) -> (&'s str, usize, char, Option<u8>) {
     let mut m = re.captures_iter(s);
     let m1 = m.next().unwrap();
 
     let label = m1.get(1).unwrap().as_str();
-    let hs = hash(label);
+    // The low byte of the spread hash is the HASH, which numbers the box
+    let hs = HolidayHash.hash_one(HolidayKey(label)) as u8;
     let op = m1.get(2).unwrap().as_str().chars().next().unwrap();
     let num_opt = m1.get(3).map(|n| n.as_str().parse::<u8>().unwrap());
 
@@ -62,7 +54,7 @@ mod part1 {
 
 mod part2 {
     use crate::{to_instr, INPUT};
-    use itertools::Itertools;
+    use aoc_2023::OrderedMap;
     use regex::Regex;
 
     pub(crate) fn solve() -> anyhow::Result<i64> {
@@ -72,54 +64,23 @@ mod part2 {
         let re = Regex::new(r"([a-z]+)([=|-])([0-9])?")?;
         let instrs = line.split(',').map(|s| to_instr(s, &re));
 
-        let mut boxes: Vec<Vec<(&str, u8)>> = vec![vec![]; 256];
+        let mut boxes: Vec<OrderedMap<&str, u8>> = vec![OrderedMap::new(); 256];
         for (label, hash, op, n) in instrs {
+            let lenses = &mut boxes[hash];
             if op == '-' {
-                let lenses = &mut boxes[hash];
-                if let Some(idx) = lenses
-                    .iter()
-                    .position(|(lens_label, _)| lens_label == &label)
-                {
-                    lenses.remove(idx);
-                }
+                lenses.remove(label);
             } else if op == '=' {
-                let focal = n.unwrap();
-                let lenses = &mut boxes[hash];
-                if let Some(idx) = lenses
-                    .iter()
-                    .position(|(lens_label, _)| lens_label == &label)
-                {
-                    lenses[idx] = (label, focal);
-                } else {
-                    lenses.push((label, focal));
-                }
-            }
-
-            print!("After: {label}{op}");
-            if let Some(n) = n {
-                println!("{n}");
-            } else {
-                println!();
-            }
-            for (idx, box_) in boxes.iter().enumerate() {
-                if !box_.is_empty() {
-                    print!("Box {idx}: ");
-                    println!(
-                        "[{}]",
-                        box_.iter().map(|(s, u)| format!("{s} {u}")).join(" ")
-                    );
-                }
+                lenses.insert(label, n.unwrap());
             }
-            println!();
         }
 
         let power = boxes
             .iter()
             .enumerate()
             .map(|(box_num, box_)| {
-                box_.iter()
+                box_.values()
                     .enumerate()
-                    .map(|(slot_idx, (_, lens))| (slot_idx + 1, lens))
+                    .map(|(slot_idx, lens)| (slot_idx + 1, lens))
                     .map(|(slot_num, lens)| (1 + box_num) * slot_num * *lens as usize)
                     .sum::<usize>()
             })
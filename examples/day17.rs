@@ -1,103 +1,32 @@
-use std::{
-    collections::{BinaryHeap, HashMap},
-    error::Error,
+use aoc_2023::{
+    crucible::{min_path_cost, RunRules},
+    Co2, Grid,
 };
 
-use aochelpers::{parse_number_grid, Coordinate, Direction, ScoredItem};
-
 const INPUT: &str = include_str!("inputs/day17.txt");
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct Crucible {
-    facing: Direction,
-    location: Coordinate<i32>,
-    steps_taken: i32,
-}
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let grid: HashMap<Coordinate<i32>, i32> = parse_number_grid(INPUT);
-    println!("{}", part1(&grid, 0, 3));
-    println!("{}", part1(&grid, 4, 10));
+fn main() -> anyhow::Result<()> {
+    let weights = parse(INPUT);
 
-    Ok(())
-}
+    let p1 = heat_loss(&weights, RunRules::crucible());
+    println!("Part 1: {p1}");
+    assert_eq!(p1, 694);
 
-fn part1(grid: &HashMap<Coordinate<i32>, i32>, min_move: i32, max_move: i32) -> i32 {
-    let mut repeated_states = HashMap::new();
-    let mut unconsidered = BinaryHeap::new();
+    let p2 = heat_loss(&weights, RunRules::ultra_crucible());
+    println!("Part 2: {p2}");
+    assert_eq!(p2, 829);
 
-    let goal = Coordinate {
-        x: grid.keys().map(|c| c.x).max().unwrap(),
-        y: grid.keys().map(|c| c.y).max().unwrap(),
-    };
+    Ok(())
+}
 
-    unconsidered.push(ScoredItem {
-        cost: 0,
-        item: Crucible {
-            facing: Direction::East,
-            location: Coordinate { x: 0, y: 0 },
-            steps_taken: 0,
-        },
-    });
-    unconsidered.push(ScoredItem {
-        cost: 0,
-        item: Crucible {
-            facing: Direction::South,
-            location: Coordinate { x: 0, y: 0 },
-            steps_taken: 0,
-        },
-    });
+fn parse(input: &str) -> Grid<u8> {
+    Grid::parse(input).map(|c| c.to_digit(10).unwrap() as u8)
+}
 
-    while let Some(current_square) = unconsidered.pop() {
-        let best_for_square = repeated_states
-            .get(&current_square.item)
-            .unwrap_or(&i32::MAX);
-        if *best_for_square <= current_square.cost {
-            continue;
-        }
-        repeated_states.insert(current_square.item, current_square.cost);
-        if current_square.item.location == goal && current_square.item.steps_taken >= min_move {
-            return current_square.cost;
-        } else if current_square.item.location == goal {
-            continue;
-        }
-        if current_square.item.steps_taken + 1 < max_move {
-            let next_square = current_square
-                .item
-                .location
-                .neighbour(current_square.item.facing);
-            if grid.contains_key(&next_square) {
-                unconsidered.push(ScoredItem {
-                    cost: current_square.cost + grid.get(&next_square).unwrap(),
-                    item: Crucible {
-                        facing: current_square.item.facing,
-                        location: next_square,
-                        steps_taken: current_square.item.steps_taken + 1,
-                    },
-                });
-            }
-        }
-        let forks = match current_square.item.facing {
-            Direction::North | Direction::South => [Direction::East, Direction::West],
-            Direction::East | Direction::West => [Direction::North, Direction::South],
-            _ => unimplemented!(),
-        };
-        if current_square.item.steps_taken + 1 >= min_move {
-            for next_direction in forks {
-                let next_square = current_square.item.location.neighbour(next_direction);
-                if grid.contains_key(&next_square) {
-                    unconsidered.push(ScoredItem {
-                        cost: current_square.cost + grid.get(&next_square).unwrap(),
-                        item: Crucible {
-                            facing: next_direction,
-                            location: next_square,
-                            steps_taken: 0,
-                        },
-                    });
-                }
-            }
-        }
-    }
-    0
+/// Least heat lost on the way from the top-left to the bottom-right block
+fn heat_loss(weights: &Grid<u8>, rules: RunRules) -> u32 {
+    let goal = Co2(weights.rows() - 1, weights.cols() - 1);
+    min_path_cost(weights, Co2(0, 0), goal, rules).expect("factory is unreachable")
 }
 
 #[cfg(test)]
@@ -120,8 +49,8 @@ mod tests {
 
     #[test]
     fn test_checksum() {
-        let grid: HashMap<Coordinate<i32>, i32> = parse_number_grid(DATA);
-        assert_eq!(part1(&grid, 0, 3), 102);
-        assert_eq!(part1(&grid, 4, 10), 94);
+        let weights = parse(DATA);
+        assert_eq!(heat_loss(&weights, RunRules::crucible()), 102);
+        assert_eq!(heat_loss(&weights, RunRules::ultra_crucible()), 94);
     }
 }
@@ -1,7 +1,7 @@
+use aoc_2023::{Co2, Rect};
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use regex::Regex;
-use std::collections::HashSet;
 
 use crate::part1::{draw_grid, interpret_part1_input};
 
@@ -71,28 +71,9 @@ fn norm(x: (isize, isize)) -> (isize, isize) {
     )
 }
 
-fn try_add(co: &Co, offset: (isize, isize), rows: usize, cols: usize) -> Option<Co> {
-    let row = co.0.checked_add_signed(offset.0);
-    let col = co.1.checked_add_signed(offset.1);
-    row.zip(col).filter(|&(row, col)| row < rows && col < cols)
-}
-
-pub(crate) fn flood_map(start: Co, grid: &[Vec<bool>], fill: &mut HashSet<Co>) {
-    let (rows, cols) = (grid.len(), grid[0].len());
-    let steplist = [(-1, 0), (1, 0), (0, -1), (0, 1)]
-        .into_iter()
-        .filter_map(|ofs| try_add(&start, ofs, rows, cols))
-        .filter(|co| !grid[co.0][co.1])
-        .filter(|co| !fill.contains(co))
-        .collect_vec();
-    for next in steplist.into_iter() {
-        fill.insert(next);
-        flood_map(next, grid, fill)
-    }
-}
-
-fn remap(pos: (isize, isize), low_row: isize, low_col: isize) -> Co {
-    ((pos.0 - low_row) as usize, (pos.1 - low_col) as usize)
+/// Moves `pos` so that `low` becomes the origin
+fn remap(pos: (isize, isize), low: Co2<isize>) -> Co {
+    ((pos.0 - low[0]) as usize, (pos.1 - low[1]) as usize)
 }
 
 fn interpret_part2_input(line: &str) -> (Dir, isize) {
@@ -171,29 +152,19 @@ fn part2(input: &str, interpret: Interpret) -> anyhow::Result<i64> {
         pos = npos;
     }
 
-    let low_row = trace
-        .iter()
-        .min_by(|pos0, pos1| pos0.0.cmp(&pos1.0))
-        .unwrap()
-        .0;
-    let low_col = trace
-        .iter()
-        .min_by(|pos0, pos1| pos0.1.cmp(&pos1.1))
+    let low = Rect::bounding(trace.iter().map(|&pos| Co2::from(pos)))
         .unwrap()
-        .1;
+        .min();
 
-    let trace = trace
-        .into_iter()
-        .map(|pos| remap(pos, low_row, low_col))
-        .collect_vec();
+    let trace = trace.into_iter().map(|pos| remap(pos, low)).collect_vec();
 
     let mut vlines = vlines
         .into_iter()
-        .map(|(start, end)| (remap(start, low_row, low_col), remap(end, low_row, low_col)))
+        .map(|(start, end)| (remap(start, low), remap(end, low)))
         .collect_vec();
     let mut hlines = hlines
         .into_iter()
-        .map(|(start, end)| (remap(start, low_row, low_col), remap(end, low_row, low_col)))
+        .map(|(start, end)| (remap(start, low), remap(end, low)))
         .collect_vec();
 
     // Sort vlines
@@ -337,8 +308,8 @@ fn main() -> anyhow::Result<()> {
 
 mod part1 {
     use crate::*;
+    use aoc_2023::{regions, Connectivity, Grid};
     use itertools::Itertools;
-    use std::collections::HashSet;
 
     pub(crate) fn interpret_part1_input(line: &str) -> (Dir, isize) {
         let mut cap = RE.captures_iter(line);
@@ -367,47 +338,28 @@ mod part1 {
             trace.push(pos);
         }
 
-        let low_row = trace
-            .iter()
-            .min_by(|pos0, pos1| pos0.0.cmp(&pos1.0))
-            .unwrap()
-            .0;
-        let low_col = trace
-            .iter()
-            .min_by(|pos0, pos1| pos0.1.cmp(&pos1.1))
+        let low = Rect::bounding(trace.iter().map(|&pos| Co2::from(pos)))
             .unwrap()
-            .1;
+            .min();
 
-        let trace = trace
-            .into_iter()
-            .map(|pos| remap(pos, low_row, low_col))
-            .collect_vec();
+        let trace = trace.into_iter().map(|pos| remap(pos, low)).collect_vec();
 
-        let mut grid = make_grid(&trace);
+        let grid = Grid::from(make_grid(&trace));
 
-        flood_fill((grid.len() / 4, grid[0].len() / 2), &mut grid);
+        // Everything not reachable from outside of the trench is dug out
+        let outside =
+            regions::flood_fill(&grid, [], Connectivity::Orthogonal, true, |_, &dug| !dug);
+        let lagoon = Grid::from_fn(grid.rows(), grid.cols(), |co| !outside.contains(co));
 
         if VIZ {
-            draw_grid(&grid);
+            print!("{}", lagoon.map(|&dug| if dug { '#' } else { '.' }));
         }
 
-        let capacity: i64 = grid
-            .into_iter()
-            .map(|line| line.iter().filter(|&&x| x).count())
-            .sum::<usize>() as i64;
+        let capacity = lagoon.cells().iter().filter(|&&dug| dug).count() as i64;
 
         Ok(capacity)
     }
 
-    pub(crate) fn flood_fill(start: (usize, usize), grid: &mut [Vec<bool>]) {
-        let mut filled = HashSet::new();
-        filled.insert(start);
-        flood_map(start, grid, &mut filled);
-        for co in filled.into_iter() {
-            grid[co.0][co.1] = true;
-        }
-    }
-
     pub(crate) fn make_grid(trace: &[(usize, usize)]) -> Vec<Vec<bool>> {
         let rows = trace
             .iter()
@@ -1,3 +1,7 @@
+use aoc_2023::{
+    dot::{self, DotGraph, NodeStyle, Shape},
+    Id, IdVec, Interner,
+};
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use regex::Regex;
@@ -17,37 +21,43 @@ struct Rule {
     rating: char,
     ord: cmp::Ordering,
     rhs: usize,
-    target_container: String,
+    target_container: Id,
 }
 
 struct Workflow {
-    name: String,
+    name: Id,
     rules: Vec<Rule>,
-    default: String,
+    default: Id,
+}
+
+/// Workflows indexed by the ID of their name
+struct System {
+    names: Interner,
+    workflows: IdVec<Workflow>,
 }
 
 impl Workflow {
-    fn apply(&self, part: &Part) -> Option<String> {
+    fn apply(&self, part: &Part) -> Option<Id> {
         for rule in &self.rules {
             if part[&rule.rating].cmp(&rule.rhs) == rule.ord {
-                return Some(rule.target_container.clone());
+                return Some(rule.target_container);
             }
         }
         None
     }
 
-    fn partitions(&self, mut part: Partition) -> Vec<(String, Partition)> {
+    fn partitions(&self, mut part: Partition) -> Vec<(Id, Partition)> {
         let mut result = vec![];
 
         // Break off partitions into matching containers
         for rule in &self.rules {
             let (a, b) = part.break_off(rule);
-            result.push((rule.target_container.clone(), a));
+            result.push((rule.target_container, a));
             part = b;
         }
 
         // Put the rest in the default bin
-        result.push((self.default.clone(), part));
+        result.push((self.default, part));
 
         result
     }
@@ -114,39 +124,78 @@ impl Partition {
 
 type Part = HashMap<char, usize>;
 
-fn build_dag(workflows: &HashMap<String, Workflow>) -> HashMap<String, Vec<String>> {
-    let mut graph = HashMap::new();
+fn build_dag(workflows: &IdVec<Workflow>) -> IdVec<Vec<Id>> {
+    workflows
+        .values()
+        .iter()
+        .map(|w| {
+            iter::once(w.default)
+                .chain(w.rules.iter().map(|rule| rule.target_container))
+                .collect_vec()
+        })
+        .collect()
+}
+
+/// Draws the workflows with each rule as a labeled edge to its target container
+fn to_dot(system: &System) -> DotGraph {
+    let name = |id| system.names.resolve(id);
+    let mut dot = DotGraph::digraph("day19");
+    dot.node("A", NodeStyle::new(Shape::DoubleCircle))
+        .node("R", NodeStyle::new(Shape::Octagon));
 
-    for (name, w) in workflows {
-        let targets = iter::once(w.default.clone())
-            .chain(w.rules.iter().map(|rule| rule.target_container.clone()))
-            .collect_vec();
-        graph.insert(name.clone(), targets);
+    for w in system
+        .workflows
+        .values()
+        .iter()
+        .sorted_by_key(|w| name(w.name))
+    {
+        let shape = if name(w.name) == "in" {
+            Shape::House
+        } else {
+            Shape::Box
+        };
+        dot.node(name(w.name), NodeStyle::new(shape));
+
+        for rule in &w.rules {
+            let ord = match rule.ord {
+                cmp::Ordering::Less => '<',
+                cmp::Ordering::Greater => '>',
+                _ => panic!(),
+            };
+            dot.labeled_edge(
+                name(w.name),
+                name(rule.target_container),
+                format!("{}{ord}{}", rule.rating, rule.rhs),
+            );
+        }
+        dot.labeled_edge(name(w.name), name(w.default), "else");
     }
 
-    graph
+    dot
 }
 
-fn resolve_partitions(init: Partition, workflows: &HashMap<String, Workflow>) -> Vec<Partition> {
+fn resolve_partitions(init: Partition, system: &System) -> Vec<Partition> {
+    let accept = system.names.get("A");
+    let reject = system.names.get("R");
     let mut out = Vec::new();
 
     let mut q = VecDeque::new();
-    q.push_back(("in".to_string(), init));
+    q.push_back((system.names.get("in").unwrap(), init));
 
     while let Some((cur, in_part)) = q.pop_front() {
-        let w = &workflows[&cur];
+        let w = &system.workflows[cur];
         for (tgt, out_part) in w.partitions(in_part) {
             // Skip rejects
-            if tgt.as_str() == "R" {
+            if Some(tgt) == reject {
                 continue;
             }
             // Store accepted separately
-            else if tgt.as_str() == "A" {
+            else if Some(tgt) == accept {
                 out.push(out_part);
             }
             // Push out partitions into next container
             else {
-                q.push_back((tgt.clone(), out_part));
+                q.push_back((tgt, out_part));
             }
         }
     }
@@ -198,13 +247,20 @@ fn union_magnitude(parts: &[Partition]) -> usize {
 
 fn main() -> anyhow::Result<()> {
     let (workflows, parts) = INPUT.split_once("\n\n").unwrap();
-    let workflows: HashMap<String, Workflow> = workflows
-        .lines()
+    let workflows = workflows.lines().collect_vec();
+    let mut names = Interner::new();
+
+    // Intern the workflow names first, so that their IDs follow the order of the lines
+    for line in &workflows {
+        names.intern(&WORKFLOW_RE.captures(line).unwrap()[1]);
+    }
+    let workflows = workflows
+        .into_iter()
         .map(|line| {
             let mut cap = WORKFLOW_RE.captures_iter(line);
             let m = cap.next().unwrap();
 
-            let name = m[1].to_string();
+            let name = names.intern(&m[1]);
             let rules = m[2].to_string();
 
             let rules_cap = RULE_RE.captures_iter(&rules);
@@ -218,7 +274,7 @@ fn main() -> anyhow::Result<()> {
                         _ => panic!(),
                     };
                     let target = rule[3].parse::<usize>().unwrap();
-                    let target_container = rule[4].to_string();
+                    let target_container = names.intern(&rule[4]);
 
                     Rule {
                         rating,
@@ -229,22 +285,25 @@ fn main() -> anyhow::Result<()> {
                 })
                 .collect_vec();
 
-            let default = m[3].to_string();
+            let default = names.intern(&m[3]);
 
-            (
-                name.clone(),
-                Workflow {
-                    name,
-                    rules,
-                    default,
-                },
-            )
+            Workflow {
+                name,
+                rules,
+                default,
+            }
         })
         .collect();
+    let system = System { names, workflows };
+
+    if dot::requested() {
+        print!("{}", to_dot(&system));
+        return Ok(());
+    }
 
     let init = Partition::default();
 
-    let accepted = resolve_partitions(init, &workflows);
+    let accepted = resolve_partitions(init, &system);
     /*
     for part in &accepted {
         println!("{:?}", part);
@@ -257,7 +316,7 @@ fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
-fn part1(workflows: &HashMap<String, Workflow>, parts_str: &str) -> i64 {
+fn part1(system: &System, parts_str: &str) -> i64 {
     let parts = parts_str
         .lines()
         .map(|line| {
@@ -289,36 +348,26 @@ fn part1(workflows: &HashMap<String, Workflow>, parts_str: &str) -> i64 {
         })
         .collect_vec();
 
-    let mut qs: HashMap<String, VecDeque<Part>> = workflows
-        .iter()
-        .map(|(name, _)| (name.clone(), VecDeque::new()))
-        .collect();
-
-    qs.insert("A".to_string(), VecDeque::new());
-    qs.insert("R".to_string(), VecDeque::new());
+    let mut qs: IdVec<VecDeque<Part>> = IdVec::from_fn(system.names.len(), |_| VecDeque::new());
+    let accept = system.names.get("A").unwrap();
 
     for part in parts {
-        let q = qs.get_mut("in").unwrap();
+        let q = &mut qs[system.names.get("in").unwrap()];
         q.push_back(part.clone());
     }
 
-    let keys = qs.keys().cloned().collect_vec();
     loop {
         let mut any_left = false;
-        for qname in &keys {
-            let q = qs.get_mut(qname).unwrap();
-            if qname == "A" || qname == "R" {
-                continue;
-            }
-            if let Some(part) = q.pop_front() {
+        // The names of the workflows were interned first, "A" and "R" come after
+        for w in system.workflows.values() {
+            if let Some(part) = qs[w.name].pop_front() {
                 any_left = true;
-                let w = &workflows[qname];
                 let next_q = if let Some(target) = w.apply(&part) {
-                    target.clone()
+                    target
                 } else {
-                    w.default.clone()
+                    w.default
                 };
-                qs.get_mut(&next_q).unwrap().push_back(part);
+                qs[next_q].push_back(part);
             }
         }
         if !any_left {
@@ -326,7 +375,7 @@ fn part1(workflows: &HashMap<String, Workflow>, parts_str: &str) -> i64 {
         }
     }
 
-    let accepted = qs.get("A").unwrap();
+    let accepted = &qs[accept];
     for part in accepted {
         println!("Accepted: {part:?}");
     }
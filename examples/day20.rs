@@ -1,3 +1,7 @@
+use aoc_2023::{
+    dot::{self, DotGraph, NodeStyle, Shape},
+    Id, IdVec, Interner,
+};
 use itertools::Itertools;
 use std::{collections::*, fmt};
 
@@ -8,7 +12,7 @@ const _TEST_INPUT: &str = include_str!("inputs/day20_test.txt");
 enum Mod {
     Flip(bool),
     UninitCon,
-    Con(HashMap<String, Pulse>),
+    Con(HashMap<Id, Pulse>),
     Inv,
     Broad,
 }
@@ -22,7 +26,7 @@ impl Mod {
         }
     }
 
-    fn resolve_pulse(&mut self, pulse_src: &str, in_pulse: Pulse) -> Option<Pulse> {
+    fn resolve_pulse(&mut self, pulse_src: Id, in_pulse: Pulse) -> Option<Pulse> {
         match self {
             Mod::Flip(state) => {
                 match in_pulse {
@@ -36,7 +40,7 @@ impl Mod {
                 }
             }
             Mod::Con(inputs) => {
-                *inputs.get_mut(pulse_src).unwrap() = in_pulse;
+                *inputs.get_mut(&pulse_src).unwrap() = in_pulse;
                 if inputs.iter().all(|(_, p)| *p == Pulse::Hi) {
                     Some(Pulse::Lo)
                 } else {
@@ -81,25 +85,38 @@ impl fmt::Debug for Pulse {
     }
 }
 
+/// Module network where the IDs of the modules precede the IDs of sinks such as `rx`
+struct Modules {
+    names: Interner,
+    mods: IdVec<(Mod, Vec<Id>)>,
+}
+
+impl Modules {
+    fn is_module(&self, id: Id) -> bool {
+        id.index() < self.mods.len()
+    }
+}
+
 /// Returns if rx low
-fn push_button(mods: &mut HashMap<String, (Mod, Vec<String>)>) -> bool {
+fn push_button(mods: &mut Modules) -> bool {
+    let rx = mods.names.get("rx");
     let mut q = VecDeque::new();
 
     q.push_back((
-        "button".to_string(),
+        mods.names.get("button").unwrap(),
         Pulse::Lo,
-        vec!["broadcaster".to_string()],
+        vec![mods.names.get("broadcaster").unwrap()],
     ));
 
     while let Some((pulse_src, pulse, pulse_target_names)) = q.pop_front() {
-        if pulse == Pulse::Lo && pulse_target_names.contains(&"rx".to_string()) {
+        if pulse == Pulse::Lo && rx.is_some_and(|rx| pulse_target_names.contains(&rx)) {
             return true;
         }
 
         // Eliminate non-existing modules before dispatching subsequent pulses from them
         let pulse_target_names = pulse_target_names
             .into_iter()
-            .filter(|in_tgt_name| mods.contains_key(in_tgt_name))
+            .filter(|&in_tgt_name| mods.is_module(in_tgt_name))
             .collect_vec();
 
         /*
@@ -109,16 +126,10 @@ fn push_button(mods: &mut HashMap<String, (Mod, Vec<String>)>) -> bool {
         */
 
         let out_pulses = pulse_target_names.into_iter().filter_map(|pulse_tgt_name| {
-            let (pulse_tgt_mod, subsequent_targets) = mods.get_mut(&pulse_tgt_name).unwrap();
+            let (pulse_tgt_mod, subsequent_targets) = &mut mods.mods[pulse_tgt_name];
             pulse_tgt_mod
-                .resolve_pulse(&pulse_src, pulse)
-                .and_then(|out_pulse| {
-                    Some((
-                        pulse_tgt_name.clone(),
-                        out_pulse,
-                        subsequent_targets.clone(),
-                    ))
-                })
+                .resolve_pulse(pulse_src, pulse)
+                .map(|out_pulse| (pulse_tgt_name, out_pulse, subsequent_targets.clone()))
         });
         q.extend(out_pulses);
     }
@@ -126,7 +137,46 @@ fn push_button(mods: &mut HashMap<String, (Mod, Vec<String>)>) -> bool {
     false
 }
 
+/// Draws the module network with a distinct shape for each module kind
+fn to_dot(mods: &Modules) -> DotGraph {
+    let mut dot = DotGraph::digraph("day20");
+    dot.node("button", NodeStyle::new(Shape::Plaintext))
+        .edge("button", "broadcaster");
+
+    let sorted = mods
+        .mods
+        .iter()
+        .map(|(id, mod_)| (mods.names.resolve(id), mod_))
+        .sorted_by_key(|(name, _)| *name);
+    for (name, (mod_, targets)) in sorted {
+        let style = match mod_ {
+            Mod::Flip(_) => NodeStyle::new(Shape::Box).with_label(format!("%{name}")),
+            Mod::Con(_) | Mod::UninitCon => {
+                NodeStyle::new(Shape::Diamond).with_label(format!("&{name}"))
+            }
+            Mod::Inv => NodeStyle::new(Shape::InvTriangle).with_label(format!("&{name}")),
+            Mod::Broad => NodeStyle::new(Shape::House),
+        };
+        dot.node(name, style);
+
+        for &tgt in targets {
+            // Sinks such as `rx` are not modules of their own
+            if !mods.is_module(tgt) {
+                dot.node(mods.names.resolve(tgt), NodeStyle::new(Shape::DoubleCircle));
+            }
+            dot.edge(name, mods.names.resolve(tgt));
+        }
+    }
+
+    dot
+}
+
 fn main() -> anyhow::Result<()> {
+    if dot::requested() {
+        print!("{}", to_dot(&parse_input(INPUT)));
+        return Ok(());
+    }
+
     assert_eq!(part1::solve(INPUT), 919383692);
 
     println!("Part 2...");
@@ -147,46 +197,53 @@ fn part2(input: &str) -> i64 {
     panic!()
 }
 
-fn parse_input(input: &str) -> HashMap<String, (Mod, Vec<String>)> {
-    let mut mods: HashMap<_, _> = input
+fn parse_input(input: &str) -> Modules {
+    let lines = input
         .lines()
         .map(|line| {
             let (lefts, rights) = line.split_once("->").unwrap();
             let lefts = lefts.trim();
-            let rights = rights.trim();
 
             let (mod_, name) = if lefts == "broadcaster" {
-                (Mod::Broad, lefts.to_string())
+                (Mod::Broad, lefts)
             } else {
-                let mut it = lefts.chars();
-                let c = it.next().unwrap();
-                (Mod::from_char(c), it.collect::<String>())
+                (Mod::from_char(lefts.chars().next().unwrap()), &lefts[1..])
             };
 
-            let targets = rights
-                .split(',')
-                .map(str::trim)
-                .map(str::to_owned)
-                .collect_vec();
+            (name, mod_, rights.split(',').map(str::trim).collect_vec())
+        })
+        .collect_vec();
 
-            (name, (mod_, targets))
+    // Intern the module names first, so that their IDs follow the order of the lines
+    let mut names = Interner::new();
+    for (name, _, _) in &lines {
+        names.intern(name);
+    }
+    let mut mods: IdVec<_> = lines
+        .into_iter()
+        .map(|(_, mod_, targets)| {
+            let targets = targets
+                .into_iter()
+                .map(|tgt| names.intern(tgt))
+                .collect_vec();
+            (mod_, targets)
         })
         .collect();
+    names.intern("button");
 
     let mut inputs = HashMap::new();
-    for (mod_name, (_, mod_targets)) in &mods {
-        for tgt in mod_targets {
+    for (mod_name, (_, mod_targets)) in mods.iter() {
+        for &tgt in mod_targets {
             inputs
-                .entry(tgt.clone())
-                .and_modify(|inputs: &mut Vec<_>| inputs.push(mod_name.clone()))
-                .or_insert(vec![mod_name.clone()]);
+                .entry(tgt)
+                .and_modify(|inputs: &mut Vec<_>| inputs.push(mod_name))
+                .or_insert(vec![mod_name]);
         }
     }
 
     for (name, inputs) in inputs.into_iter() {
-        if let Some(mod_) = mods.get(&name) {
-            if mod_.0 == Mod::UninitCon {
-                let con = mods.get_mut(&name).unwrap();
+        if let Some(con) = mods.get_mut(name) {
+            if con.0 == Mod::UninitCon {
                 if inputs.len() > 1 {
                     con.0 = Mod::Con(inputs.into_iter().map(|in_| (in_, Pulse::Lo)).collect());
                 } else {
@@ -196,32 +253,30 @@ fn parse_input(input: &str) -> HashMap<String, (Mod, Vec<String>)> {
         }
     }
 
-    mods
+    Modules { names, mods }
 }
 
 mod part1 {
     use itertools::Itertools;
 
-    use crate::{parse_input, Mod, Pulse};
-    use std::collections::{HashMap, VecDeque};
+    use crate::{parse_input, Modules, Pulse};
+    use aoc_2023::Id;
+    use std::collections::VecDeque;
 
     /// Returns (low pulses, high pulses)
-    fn push_button(mods: &mut HashMap<String, (Mod, Vec<String>)>) -> (usize, usize) {
+    fn push_button(mods: &mut Modules) -> (usize, usize) {
         let mut q = VecDeque::new();
 
         q.push_back((
-            "button".to_string(),
+            mods.names.get("button").unwrap(),
             Pulse::Lo,
-            vec!["broadcaster".to_string()],
+            vec![mods.names.get("broadcaster").unwrap()],
         ));
 
         simulate(q, mods)
     }
 
-    fn simulate(
-        mut q: VecDeque<(String, Pulse, Vec<String>)>,
-        mods: &mut HashMap<String, (Mod, Vec<String>)>,
-    ) -> (usize, usize) {
+    fn simulate(mut q: VecDeque<(Id, Pulse, Vec<Id>)>, mods: &mut Modules) -> (usize, usize) {
         let mut lo_pulses = 0;
         let mut hi_pulses = 0;
 
@@ -236,7 +291,7 @@ mod part1 {
             // Eliminate non-existing modules before dispatching subsequent pulses from them
             let pulse_target_names = pulse_target_names
                 .into_iter()
-                .filter(|in_tgt_name| mods.contains_key(in_tgt_name))
+                .filter(|&in_tgt_name| mods.is_module(in_tgt_name))
                 .collect_vec();
 
             /*
@@ -246,16 +301,10 @@ mod part1 {
             */
 
             let out_pulses = pulse_target_names.into_iter().filter_map(|pulse_tgt_name| {
-                let (pulse_tgt_mod, subsequent_targets) = mods.get_mut(&pulse_tgt_name).unwrap();
+                let (pulse_tgt_mod, subsequent_targets) = &mut mods.mods[pulse_tgt_name];
                 pulse_tgt_mod
-                    .resolve_pulse(&pulse_src, pulse)
-                    .and_then(|out_pulse| {
-                        Some((
-                            pulse_tgt_name.clone(),
-                            out_pulse,
-                            subsequent_targets.clone(),
-                        ))
-                    })
+                    .resolve_pulse(pulse_src, pulse)
+                    .map(|out_pulse| (pulse_tgt_name, out_pulse, subsequent_targets.clone()))
             });
             q.extend(out_pulses);
         }
@@ -264,7 +313,7 @@ mod part1 {
     }
 
     pub(crate) fn solve(input: &str) -> i64 {
-        let mut mods: HashMap<String, (Mod, Vec<String>)> = parse_input(input);
+        let mut mods = parse_input(input);
 
         let (lo_pulses, hi_pulses) = (0..1000)
             .map(|_| push_button(&mut mods))
@@ -1,3 +1,4 @@
+use aoc_2023::{Co2, Grid};
 use itertools::Itertools;
 use std::{
     cmp,
@@ -7,55 +8,6 @@ use std::{
 const INPUT: &str = include_str!("inputs/day21.txt");
 const TEST_INPUT: &str = include_str!("inputs/day21_test.txt");
 
-type Co = (usize, usize);
-
-fn wrap(co: &(isize, isize), rows: usize, cols: usize) -> Co {
-    let rows = rows as isize;
-    let cols = cols as isize;
-
-    let mut row = co.0 % rows;
-    let mut col = co.1 % cols;
-
-    if row.is_negative() {
-        row = rows + row;
-    }
-    if col.is_negative() {
-        col = cols + col;
-    }
-
-    (row as usize, col as usize)
-}
-
-fn reach(
-    start: (isize, isize),
-    step_count: usize,
-    grid: &[Vec<char>],
-    cheat: bool,
-) -> Vec<(isize, isize)> {
-    let rows = grid.len();
-    let cols = grid[0].len();
-
-    let mut cos = vec![(start.0 as isize, start.1 as isize)];
-    for _n in 0..step_count {
-        cos = cos
-            .iter()
-            // Find coordinates reachable from this position
-            .flat_map(|co| {
-                [(-1, 0), (1, 0), (0, -1), (0, 1)]
-                    .into_iter()
-                    .filter_map(move |ofs| {
-                        let inext = (co.0 + ofs.0, co.1 + ofs.1);
-                        let next_grid_co = wrap(&inext, rows, cols);
-                        (grid[next_grid_co.0][next_grid_co.1] == '.').then_some(inext)
-                    })
-            })
-            .unique()
-            .collect_vec();
-    }
-
-    cos
-}
-
 #[derive(Clone, Eq, PartialEq)]
 struct Reach {
     // `n` steps reaches...
@@ -84,23 +36,10 @@ fn main() -> anyhow::Result<()> {
     let p1 = part1::solve();
     assert_eq!(p1, 3658);
 
-    /*
-    let (test_grid, test_start) = parse_input(TEST_INPUT);
-    let test_start = (test_start.0 as isize, test_start.1 as isize);
-    for step_count in [6, 10, 50, 100, 500, 1000, 5000] {
-        let r = reach(test_start, step_count, &test_grid).len();
-        println!("{} -> {}", step_count, r);
-    }
-    */
-
     let (grid, start) = parse_input(INPUT);
-    let start = (start.0 as isize, start.1 as isize);
-
-    let (rows, cols) = (grid.len(), grid[0].len());
-    let parity0_grid = (0..rows).map(|row| (0..cols).map(|col| {}));
-
-    let r = reach(start, 26501365, &grid, true).len();
-    println!("Part 2: {r}");
+    let p2 = part2::solve(&Grid::from(grid), Co2::from(start), 26501365);
+    assert_eq!(p2, 608193767979991);
+    println!("Part 2: {p2}");
 
     Ok(())
 }
@@ -130,41 +69,40 @@ fn parse_input(input: &str) -> (Vec<Vec<char>>, (usize, usize)) {
 }
 
 mod part1 {
-    use itertools::Itertools;
-
-    use crate::{parse_input, Co, INPUT};
-
-    fn try_add(co: &Co, offset: (isize, isize), rows: usize, cols: usize) -> Option<Co> {
-        let row = co.0.checked_add_signed(offset.0);
-        let col = co.1.checked_add_signed(offset.1);
-        row.zip(col).filter(|&(row, col)| row < rows && col < cols)
-    }
-
-    fn reach(start: Co, step_count: usize, grid: &[Vec<char>]) -> usize {
-        let rows = grid.len();
-        let cols = grid[0].len();
-
-        let mut cos = vec![start];
-        for _n in 0..step_count {
-            cos = cos
-                .iter()
-                // Find coordinates reachable from this position
-                .flat_map(|co| {
-                    [(-1, 0), (1, 0), (0, -1), (0, 1)]
-                        .into_iter()
-                        .filter_map(move |ofs| try_add(&co, ofs, rows, cols))
-                        .filter(|next| grid[next.0][next.1] == '.')
-                })
-                .unique()
-                .collect_vec();
-        }
-
-        cos.len()
-    }
+    use crate::{parse_input, INPUT};
+    use aoc_2023::{bfs_distances, Co2, Grid};
 
     pub(crate) fn solve() -> i64 {
         let (grid, start) = parse_input(INPUT);
-        reach(start, 64, &grid) as i64
+        let grid = Grid::from(grid);
+        let dist = bfs_distances(&grid, [Co2::from(start)], |_, &c| c == '.');
+        dist.reachable_in_exactly(64) as i64
+    }
+}
+
+mod part2 {
+    use aoc_2023::{bfs_distances, sequence::Polynomial, Co2, Grid};
+
+    /// The garden is square with the start in the middle and a clear row and column through it.
+    /// After reaching the first edge, the reach grows quadratically with each further garden
+    /// width walked, so three samples fit the whole sequence.
+    pub(crate) fn solve(grid: &Grid<char>, start: Co2<usize>, steps: u32) -> i128 {
+        let size = grid.rows();
+
+        // Enough copies of the garden to walk two garden widths past the first edge
+        let tiles = 5;
+        let tiled = Grid::from_fn(size * tiles, size * tiles, |co| {
+            grid[Co2(co[0] % size, co[1] % size)]
+        });
+        let center = Co2(start[0] + size * (tiles / 2), start[1] + size * (tiles / 2));
+        let dist = bfs_distances(&tiled, [center], |_, &c| c == '.');
+
+        let size = size as u32;
+        let samples = (0..3)
+            .map(|widths| dist.reachable_in_exactly(steps % size + widths * size) as i64)
+            .collect::<Vec<_>>();
+        let growth = Polynomial::fit(&samples).unwrap();
+        growth.eval((steps / size) as i64).unwrap()
     }
 }
 
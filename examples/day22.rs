@@ -1,171 +1,72 @@
+use aoc_2023::{Co3, Cuboid, Grid3};
 use itertools::Itertools;
-use std::{
-    collections::HashSet,
-    fmt,
-    ops::{Deref, DerefMut, RangeInclusive},
-};
+use std::{collections::HashSet, fmt};
 
 const INPUT: &str = include_str!("inputs/day22.txt");
 const _TEST_INPUT: &str = include_str!("inputs/day22_test.txt");
 
-type R = (usize, usize);
-
-fn try_add(
-    co: &(usize, usize, usize),
-    offset: Offset,
-    xs: usize,
-    ys: usize,
-    zs: usize,
-) -> Option<Co> {
-    let x = co.0.checked_add_signed(offset.0);
-    let y = co.1.checked_add_signed(offset.1);
-    let z = co.2.checked_add_signed(offset.2);
-    x.zip(y)
-        .zip(z)
-        .map(|((x, y), z)| (x, y, z))
-        .filter(|&(x, y, z)| (x < xs && y < ys && z < zs))
-        .map(|(x, y, z)| Co((x, y, z)))
-}
-
-struct Co((usize, usize, usize));
-
-impl Deref for Co {
-    type Target = (usize, usize, usize);
-
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
-
-impl DerefMut for Co {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
-    }
-}
+/// Bricks by index, stacked in a tower of cubes
+type Tower = Grid3<Option<usize>>;
 
-impl Co {
-    pub fn as_tuple(&self) -> (usize, usize, usize) {
-        (self.0 .0, self.0 .1, self.0 .2)
-    }
-}
-
-type Offset = (isize, isize, isize);
-
-impl From<(usize, usize, usize)> for Co {
-    fn from(value: (usize, usize, usize)) -> Self {
-        Self(value)
-    }
-}
+type Offset = Co3<isize>;
 
 #[derive(Clone)]
-struct Block {
-    xs: R,
-    ys: R,
-    zs: R,
-}
+struct Block(Cuboid<usize>);
 
 impl fmt::Debug for Block {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (min, max) = (self.0.min(), self.0.max());
         f.debug_struct("Block")
-            .field("x", &(self.xs.0, self.xs.1))
-            .field("y", &(self.ys.0, self.ys.1))
-            .field("z", &(self.zs.0, self.zs.1))
+            .field("x", &(min[0], max[0]))
+            .field("y", &(min[1], max[1]))
+            .field("z", &(min[2], max[2]))
             .finish()
     }
 }
 
 impl Block {
-    fn from_cos(a: Co, b: Co) -> Self {
-        let (a, b) = (a.as_tuple(), b.as_tuple());
-        assert!(a.0 <= b.0 && a.1 <= b.1 && a.2 <= b.2);
-        let xs = (a.0, b.0);
-        let ys = (a.1, b.1);
-        let zs = (a.2, b.2);
-        let ret = Self { xs, ys, zs };
+    fn from_cos(a: Co3<usize>, b: Co3<usize>) -> Self {
+        assert!(a[0] <= b[0] && a[1] <= b[1] && a[2] <= b[2]);
+        let ret = Self(Cuboid::new(a, b));
         ret.validate();
         ret
     }
 
     fn validate(&self) {
-        let differ_count = if self.xs.0 != self.xs.1 { 1 } else { 0 }
-            + if self.ys.0 != self.ys.1 { 1 } else { 0 }
-            + if self.zs.0 != self.zs.1 { 1 } else { 0 };
-        assert!(
-            (differ_count == 1)
-                || (self.xs.0 == self.xs.1 && self.ys.0 == self.ys.1 && self.zs.0 == self.zs.1),
-            "{:?}",
-            &self
-        );
+        // Bricks are a single line of cubes
+        let differ_count = self.0.extent().iter().filter(|&&len| len != 1).count();
+        assert!(differ_count <= 1, "{:?}", &self);
     }
 
-    fn validate_with_grid(&self, idx: usize, grid: &mut [Vec<Vec<Option<usize>>>]) {
+    fn validate_with_grid(&self, idx: usize, grid: &Tower) {
         self.validate();
-        for (x, y, z) in self.coords() {
-            assert_eq!(grid[z][y][x], Some(idx));
+        for co in self.coords() {
+            assert_eq!(grid[co], Some(idx));
         }
     }
 
-    fn xmax(&self) -> usize {
-        self.xs.1
-    }
-    fn ymax(&self) -> usize {
-        self.ys.1
-    }
     fn zmin(&self) -> usize {
-        self.zs.0
-    }
-    fn zmax(&self) -> usize {
-        self.zs.1
-    }
-
-    fn zs(&self) -> RangeInclusive<usize> {
-        self.zs.0..=self.zs.1
-    }
-    fn ys(&self) -> RangeInclusive<usize> {
-        self.ys.0..=self.ys.1
-    }
-    fn xs(&self) -> RangeInclusive<usize> {
-        self.xs.0..=self.xs.1
+        self.0.min()[2]
     }
 
     fn translated(&self, offset: Offset) -> Option<Block> {
-        let t = |v: usize, o: isize| v.checked_add_signed(o);
-
-        let (xmin, xmax) = (t(self.xs.0, offset.0)?, t(self.xs.1, offset.0)?);
-        let (ymin, ymax) = (t(self.ys.0, offset.1)?, t(self.ys.1, offset.1)?);
-        let (zmin, zmax) = (t(self.zs.0, offset.2)?, t(self.zs.1, offset.2)?);
-
-        let ret = Block {
-            xs: (xmin, xmax),
-            ys: (ymin, ymax),
-            zs: (zmin, zmax),
-        };
+        let ret = Block(self.0.translate(offset)?);
         ret.validate();
         Some(ret)
     }
 
-    fn coords(&self) -> impl Iterator<Item = (usize, usize, usize)> {
-        self.zs()
-            .cartesian_product(self.ys())
-            .cartesian_product(self.xs())
-            .map(|((z, y), x)| (x, y, z))
+    fn coords(&self) -> impl Iterator<Item = Co3<usize>> {
+        self.0.points()
     }
 }
 
-fn render(blocks: &[Block]) -> Vec<Vec<Vec<Option<usize>>>> {
-    let xlen = blocks.iter().map(|b| b.xmax()).max().unwrap() + 1;
-    let ylen = blocks.iter().map(|b| b.ymax()).max().unwrap() + 1;
-    let zlen = blocks.iter().map(|b| b.zmax()).max().unwrap() + 1;
-
-    let mut grid = vec![vec![vec![None; xlen]; ylen]; zlen];
+fn render(blocks: &[Block]) -> Tower {
+    let bounds = blocks.iter().map(|b| b.0).reduce(|a, b| a.union_bounds(&b));
+    let mut grid = Grid3::new(bounds.unwrap().max() + Co3(1, 1, 1), None);
 
     for (idx, b) in blocks.iter().enumerate() {
-        for z in b.zs() {
-            for y in b.ys() {
-                for x in b.xs() {
-                    grid[z][y][x] = Some(idx);
-                }
-            }
+        for co in b.coords() {
+            grid[co] = Some(idx);
         }
     }
 
@@ -173,60 +74,24 @@ fn render(blocks: &[Block]) -> Vec<Vec<Vec<Option<usize>>>> {
 }
 
 /// N.b., block must match block idx in grid
-fn translate(block: &mut Block, offset: Offset, idx: usize, grid: &mut [Vec<Vec<Option<usize>>>]) {
+fn translate(block: &mut Block, offset: Offset, idx: usize, grid: &mut Tower) {
     block.validate_with_grid(idx, grid);
-    let ncoords = block
-        .coords()
-        .map(|(x, y, z)| {
-            try_add(
-                &(x, y, z),
-                offset,
-                grid[0][0].len(),
-                grid[0].len(),
-                grid.len(),
-            )
-            .unwrap()
-            .as_tuple()
-        })
-        .collect_vec();
+    let moved = block.translated(offset).unwrap();
     erase_coords(block.coords(), grid, idx);
-    render_coords(ncoords.into_iter(), grid, idx);
-    *block = block.translated(offset).unwrap();
+    render_coords(moved.coords(), grid, idx);
+    *block = moved;
     block.validate_with_grid(idx, grid);
 }
 
-fn render_coords(
-    coords: impl Iterator<Item = (usize, usize, usize)>,
-    grid: &mut [Vec<Vec<Option<usize>>>],
-    idx: usize,
-) {
-    for (x, y, z) in coords {
-        assert_eq!(grid[z][y][x], None);
-        grid[z][y][x] = Some(idx);
-    }
-}
-
-fn erase_coords(
-    coords: impl Iterator<Item = (usize, usize, usize)>,
-    grid: &mut [Vec<Vec<Option<usize>>>],
-    idx: usize,
-) {
-    for (x, y, z) in coords {
-        assert_eq!(grid[z][y][x], Some(idx));
-        grid[z][y][x] = None;
+fn render_coords(coords: impl Iterator<Item = Co3<usize>>, grid: &mut Tower, idx: usize) {
+    for co in coords {
+        assert_eq!(grid.set(co, Some(idx)), Some(None));
     }
 }
 
-fn _print_zlayer(z: usize, grid: &[Vec<Vec<Option<usize>>>]) {
-    for yline in &grid[z] {
-        for x in yline {
-            if let Some(n) = x {
-                print!("{n}");
-            } else {
-                print!(" ");
-            }
-        }
-        println!();
+fn erase_coords(coords: impl Iterator<Item = Co3<usize>>, grid: &mut Tower, idx: usize) {
+    for co in coords {
+        assert_eq!(grid.set(co, None), Some(Some(idx)));
     }
 }
 
@@ -281,37 +146,27 @@ fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
-fn remove_block(
-    idx: usize,
-    blocks: &mut Vec<(usize, Block)>,
-    grid: &mut Vec<Vec<Vec<Option<usize>>>>,
-) {
+fn remove_block(idx: usize, blocks: &mut Vec<(usize, Block)>, grid: &mut Tower) {
     let remblock = blocks.swap_remove(idx).1;
     erase_coords(remblock.coords(), grid, idx);
 }
 
-fn _print_tower(grid: &Vec<Vec<Vec<Option<usize>>>>) {
-    println!("---");
-    for z in (0..grid.len()).rev() {
-        _print_zlayer(z, grid);
-        println!("---");
-    }
+fn _print_tower(grid: &Tower) {
+    let label = |&idx: &usize| char::from_digit((idx % 36) as u32, 36).unwrap();
+    println!("{}", grid.side_view_xz(label));
+    println!("{}", grid.side_view_yz(label));
 }
 
-fn simulates(
-    blocks: &mut [(usize, Block)],
-    grid: &mut [Vec<Vec<Option<usize>>>],
-    actuate: bool,
-) -> Vec<usize> {
+fn simulates(blocks: &mut [(usize, Block)], grid: &mut Tower, actuate: bool) -> Vec<usize> {
     let mut moved_blocks = vec![];
     for (idx, b) in blocks.iter_mut() {
-        if let Some(below) = b.translated((0, 0, -1)) {
+        if let Some(below) = b.translated(Co3(0, 0, -1)) {
             let can_move = below
                 .coords()
-                .all(|(x, y, z)| grid[z][y][x].is_none() || grid[z][y][x] == Some(*idx));
+                .all(|co| grid[co].is_none() || grid[co] == Some(*idx));
             if can_move {
                 if actuate {
-                    translate(b, (0, 0, -1), *idx, grid);
+                    translate(b, Co3(0, 0, -1), *idx, grid);
                 }
                 //println!("{idx} would move");
                 moved_blocks.push(*idx);
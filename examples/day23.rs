@@ -1,3 +1,7 @@
+use aoc_2023::{
+    dot::{self, DotGraph, NodeStyle, Shape},
+    graphs, Grid,
+};
 use itertools::Itertools;
 use std::collections::{HashMap, VecDeque};
 
@@ -154,26 +158,15 @@ fn find_longest_path(start: Co, ends: &[Co], grid: &[Vec<char>]) -> Vec<((usize,
 }
 
 fn find_crossings(grid: &[Vec<char>]) -> Vec<Co> {
-    let rows = grid.len();
-    let cols = grid[0].len();
-
-    let mut crossings = vec![];
-    for (row, line) in grid.iter().enumerate() {
-        for (col, c) in line.iter().enumerate() {
-            if c != &'#'
-                && [(-1isize, 0isize), (1, 0), (0, -1), (0, 1)]
-                    .into_iter()
-                    .filter_map(|ofs| try_add(&(row, col), ofs, rows, cols))
-                    .filter(|co| grid[co.0][co.1] != '#')
-                    .count()
-                    > 2
-            {
-                crossings.push((row, col));
-            }
-        }
-    }
-
-    crossings
+    let grid = Grid::from(grid.to_vec());
+    let paths = graphs::grid_graphmap(&grid, |c| *c != '#', graphs::cardinal);
+
+    paths
+        .nodes()
+        .filter(|&co| paths.neighbors(co).count() > 2)
+        .map(|co| co.as_tuple())
+        .sorted()
+        .collect()
 }
 
 #[derive(Debug, Clone)]
@@ -234,27 +227,41 @@ fn make_graph(start: Co, end: Co, grid: &[Vec<char>]) -> HashMap<Co, Vec<Edge>>
     edges
 }
 
-fn find_longest(start: Co, end: Co, src_graph: HashMap<Co, Vec<Edge>>) -> usize {
-    use petgraph::{algo, prelude::*};
-
-    let mut graph = DiGraph::<Co, usize>::new();
+/// Draws the junction graph with path lengths as edge labels
+fn to_dot(start: Co, end: Co, graph: &HashMap<Co, Vec<Edge>>) -> DotGraph {
+    let id = |co: &Co| format!("{},{}", co.0, co.1);
 
-    for (node, _) in src_graph.iter() {
-        graph.add_node(*node);
+    let mut dot = DotGraph::graph("day23");
+    for node in graph.keys().sorted() {
+        let shape = if *node == start || *node == end {
+            Shape::DoubleCircle
+        } else {
+            Shape::Circle
+        };
+        dot.node(id(node), NodeStyle::new(shape));
     }
-    for (node, edges) in src_graph.iter() {
-        let node = graph.node_indices().find(|i| graph[*i] == *node).unwrap();
-        for Edge(dest, wgt) in edges {
-            graph.add_edge(
-                node,
-                graph.node_indices().find(|i| graph[*i] == *dest).unwrap(),
-                *wgt,
-            );
+    // Edges are found from both ends, only draw each once
+    for (node, edges) in graph.iter().sorted_by_key(|(node, _)| *node) {
+        for Edge(dest, wgt) in edges.iter().filter(|Edge(dest, _)| node < dest) {
+            dot.labeled_edge(id(node), id(dest), wgt);
         }
     }
 
-    let start = graph.node_indices().find(|i| graph[*i] == start).unwrap();
-    let end = graph.node_indices().find(|i| graph[*i] == end).unwrap();
+    dot
+}
+
+fn find_longest(start: Co, end: Co, src_graph: HashMap<Co, Vec<Edge>>) -> usize {
+    use petgraph::algo;
+
+    let (graph, nodes) = graphs::from_edge_list(
+        src_graph
+            .iter()
+            .flat_map(|(node, edges)| edges.iter().map(|Edge(dest, wgt)| (*node, *dest, *wgt))),
+        [],
+    );
+
+    let start = nodes[&start];
+    let end = nodes[&end];
     let paths =
         algo::all_simple_paths::<Vec<_>, _>(&graph, start, end, 0, None).collect::<Vec<_>>();
     let paths = paths
@@ -289,6 +296,11 @@ fn main() -> anyhow::Result<()> {
     let start = (0, 1);
     let end = (grid.len() - 1, grid.len() - 2);
 
+    if dot::requested() {
+        print!("{}", to_dot(start, end, &make_graph(start, end, &grid)));
+        return Ok(());
+    }
+
     let longest = dfs_longest_path(vec![start], &[end], &grid, None)
         .unwrap()
         .len()
@@ -1,65 +1,14 @@
+use aoc_2023::{Co2, Co3, Rect};
 use itertools::Itertools;
 
 const INPUT: &str = include_str!("inputs/day24.txt");
 const _TEST_INPUT: &str = include_str!("inputs/day24_test.txt");
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct V2((i64, i64));
+type V2 = Co2<i64>;
+type V3 = Co3<i64>;
 
-#[derive(Debug)]
-struct F2((f64, f64));
-
-impl std::ops::Sub for V2 {
-    type Output = V2;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        V2((self.0 .0 - rhs.0 .0, self.0 .1 - rhs.0 .1))
-    }
-}
-
-impl V2 {
-    fn as_tuple(&self) -> (i64, i64) {
-        self.0
-    }
-}
-
-impl From<V2> for F2 {
-    fn from(value: V2) -> Self {
-        F2((value.0 .0 as f64, value.0 .1 as f64))
-    }
-}
-
-impl std::ops::Mul<f64> for F2 {
-    type Output = F2;
-
-    fn mul(self, rhs: f64) -> Self::Output {
-        F2((self.0 .0 * rhs, self.0 .1 * rhs))
-    }
-}
-
-impl std::ops::Add for F2 {
-    type Output = F2;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        F2((self.0 .0 + rhs.0 .0, self.0 .1 + rhs.0 .1))
-    }
-}
-
-impl std::ops::Mul<f64> for V2 {
-    type Output = F2;
-
-    fn mul(self, rhs: f64) -> Self::Output {
-        F2::from(self) * rhs
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct V3((i64, i64, i64));
-
-impl V3 {
-    fn trunc(self) -> V2 {
-        V2((self.0 .0, self.0 .1))
-    }
+fn trunc(v: V3) -> V2 {
+    Co2(v[0], v[1])
 }
 
 enum Intersect {
@@ -117,16 +66,17 @@ fn main() -> anyhow::Result<()> {
                 vel_it.next().unwrap(),
             );
 
-            (V3((x, y, z)), V3((vx, vy, vz)))
+            (Co3(x, y, z), Co3(vx, vy, vz))
         })
         .collect_vec();
 
     let min: f64 = 7f64; //200000000000000f64;
     let max: f64 = 27f64; //400000000000000f64;
+    let test_area = Rect::new(Co2(min, min), Co2(max, max));
 
     let hailstones_2d = hailstones
         .iter()
-        .map(|(x0, v0)| (x0.trunc(), v0.trunc()))
+        .map(|(x0, v0)| (trunc(*x0), trunc(*v0)))
         .collect_vec();
 
     let mut count = 0;
@@ -136,7 +86,7 @@ fn main() -> anyhow::Result<()> {
             let (x1, v1) = hailstones_2d[idx1];
             match future_intersect(x0, v0, x1, v1) {
                 Intersect::Future((px, py)) => {
-                    if px >= min && px <= max && py >= min && py <= max {
+                    if test_area.contains(Co2(px, py)) {
                         println!(
                             "Hailstones {:?} and {:?} will cross inside the test area (at {:?})",
                             x0,
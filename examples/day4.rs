@@ -35,7 +35,7 @@ mod part1 {
             .iter()
             .map(|(winning, mine)| {
                 let count = winning.intersection(mine).count() as i64;
-                
+
                 match count {
                     n @ (0 | 1) => n,
                     n => 2i64.pow((n - 1) as u32),
@@ -47,36 +47,34 @@ mod part1 {
 }
 
 mod part2 {
+    use aoc_2023::Memo;
     use std::collections::HashSet;
 
     pub(crate) fn solve(cards: &[(HashSet<i64>, HashSet<i64>)]) -> anyhow::Result<i64> {
-        let mut pmemo: Vec<Option<i64>> = vec![None; cards.len()];
+        let mut memo = Memo::new();
         let sum: i64 = (0..cards.len())
-            .map(|idx| full_count(idx, cards, &mut pmemo))
+            .map(|idx| full_count(idx, cards, &mut memo))
             .sum();
 
         Ok(sum)
     }
 
+    /// Counts card `idx` and every copy it wins, recursively
     fn full_count(
         idx: usize,
         cards: &[(HashSet<i64>, HashSet<i64>)],
-        pmemo: &mut Vec<Option<i64>>,
+        memo: &mut Memo<usize, i64>,
     ) -> i64 {
-        if let Some(points) = pmemo[idx] {
-            points
-        } else {
+        memo.get_or_compute(idx, |memo, &idx| {
             let (winning, mine) = &cards[idx];
-            let full_count = match winning.intersection(mine).count() {
+            match winning.intersection(mine).count() {
                 0 => 1,
                 n => {
                     1 + ((idx + 1)..(idx + 1 + n))
-                        .map(|idx| full_count(idx, cards, pmemo))
+                        .map(|idx| full_count(idx, cards, memo))
                         .sum::<i64>()
                 }
-            };
-            pmemo[idx] = Some(full_count);
-            full_count
-        }
+            }
+        })
     }
 }
@@ -1,22 +1,31 @@
+use aoc_2023::{
+    dot::{self, DotGraph, NodeStyle, Shape},
+    Id, IdVec, Interner,
+};
 use itertools::Itertools;
 use regex::Regex;
-use std::collections::*;
 
 const INPUT: &str = include_str!("inputs/day8.txt");
 
-fn step(cur: impl AsRef<str>, i: char, nodes: &HashMap<String, (String, String)>) -> &String {
+/// Node names with the left and right neighbor of each node
+struct Network {
+    names: Interner,
+    nodes: IdVec<(Id, Id)>,
+}
+
+fn step(cur: Id, i: char, nodes: &IdVec<(Id, Id)>) -> Id {
     if i == 'L' {
-        &nodes[cur.as_ref()].0
+        nodes[cur].0
     } else {
-        &nodes[cur.as_ref()].1
+        nodes[cur].1
     }
 }
 
 fn count_until_cond(
-    start: &str,
+    start: Id,
     instr: &[char],
-    nodes: &HashMap<String, (String, String)>,
-    cond: impl Fn(&str) -> bool,
+    nodes: &IdVec<(Id, Id)>,
+    cond: impl Fn(Id) -> bool,
 ) -> i64 {
     let mut n = 0;
     let mut cur = start;
@@ -33,6 +42,32 @@ fn count_until_cond(
     n
 }
 
+fn to_dot(net: &Network) -> DotGraph {
+    let mut dot = DotGraph::digraph("day8");
+    for (node, (left, right)) in net
+        .nodes
+        .iter()
+        .sorted_by_key(|(id, _)| net.names.resolve(*id))
+    {
+        let (node, left, right) = (
+            net.names.resolve(node),
+            net.names.resolve(*left),
+            net.names.resolve(*right),
+        );
+        let shape = if node.ends_with('A') {
+            Shape::Box
+        } else if node.ends_with('Z') {
+            Shape::DoubleCircle
+        } else {
+            Shape::Ellipse
+        };
+        dot.node(node, NodeStyle::new(shape))
+            .labeled_edge(node, left, 'L')
+            .labeled_edge(node, right, 'R');
+    }
+    dot
+}
+
 fn main() -> anyhow::Result<()> {
     let mut lines = INPUT.lines();
     let instr = lines.next().unwrap().chars().collect_vec();
@@ -41,50 +76,76 @@ fn main() -> anyhow::Result<()> {
     lines.next().unwrap();
 
     let alpha_re = Regex::new(r"[A-Z]+")?;
+    let lines = lines.collect_vec();
+    let mut names = Interner::new();
+
+    // Intern the sources first, so that their IDs follow the order of the lines
+    for s in &lines {
+        names.intern(s.split_once('=').unwrap().0.trim());
+    }
     let nodes = lines
+        .iter()
         .map(|s| {
-            let (left, right) = s.split_once('=').unwrap();
-
-            let src = left.trim().to_owned();
-
-            let mut dests = alpha_re.captures_iter(right);
-            let dest1 = dests.next().unwrap()[0].to_owned();
-            let dest2 = dests.next().unwrap()[0].to_owned();
-
-            (src, (dest1, dest2))
+            let mut dests = alpha_re.captures_iter(s.split_once('=').unwrap().1);
+            let dest1 = names.intern(&dests.next().unwrap()[0]);
+            let dest2 = names.intern(&dests.next().unwrap()[0]);
+            (dest1, dest2)
         })
         .collect();
+    let net = Network { names, nodes };
+
+    if dot::requested() {
+        print!("{}", to_dot(&net));
+        return Ok(());
+    }
 
-    println!("Part 1: {}", part1::solve(&instr, &nodes));
-    println!("Part 2: {}", part2::solve(&instr, &nodes));
+    let p1 = part1::solve(&instr, &net);
+    println!("Part 1: {p1}");
+    assert_eq!(p1, 18827);
+
+    let p2 = part2::solve(&instr, &net);
+    println!("Part 2: {p2}");
+    assert_eq!(p2, 20220305520997);
 
     Ok(())
 }
 
 mod part1 {
-    use crate::count_until_cond;
-    use std::collections::*;
+    use crate::{count_until_cond, Network};
 
-    pub(crate) fn solve(instr: &[char], nodes: &HashMap<String, (String, String)>) -> i64 {
-        count_until_cond("AAA", instr, nodes, |s| s == "ZZZ")
+    pub(crate) fn solve(instr: &[char], net: &Network) -> i64 {
+        let zzz = net.names.get("ZZZ").unwrap();
+        count_until_cond(net.names.get("AAA").unwrap(), instr, &net.nodes, |id| {
+            id == zzz
+        })
     }
 }
 
 mod part2 {
-    use crate::count_until_cond;
+    use crate::{step, Network};
+    use aoc_2023::{first_common_hit, WalkCycle};
     use itertools::Itertools;
-    use lcmx::lcmx;
-    use std::collections::HashMap;
 
-    pub(crate) fn solve(instr: &[char], nodes: &HashMap<String, (String, String)>) -> i64 {
-        let starts = nodes.keys().filter(|s| s.ends_with('A'));
+    pub(crate) fn solve(instr: &[char], net: &Network) -> i64 {
+        let is_end = |id| net.names.resolve(id).ends_with('Z');
+        let starts = net
+            .names
+            .iter()
+            .filter(|(_, name)| name.ends_with('A'))
+            .map(|(id, _)| id);
 
+        // Don't assume that the ghosts' cycles start from zero, solve for all of the Z-hits
         let cycles = starts
-            .map(|start| count_until_cond(start, instr, nodes, |s| s.ends_with('Z')) as u64)
+            .map(|start| {
+                WalkCycle::analyze(
+                    start,
+                    instr.len(),
+                    |&cur, i| step(cur, instr[i], &net.nodes),
+                    |&id| is_end(id),
+                )
+            })
             .collect_vec();
 
-        let lcm = lcmx(&cycles).unwrap();
-
-        lcm as i64
+        first_common_hit(&cycles).unwrap() as i64
     }
 }
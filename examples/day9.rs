@@ -1,4 +1,4 @@
-use itertools::Itertools;
+use aoc_2023::sequence::Polynomial;
 
 const INPUT: &str = include_str!("inputs/day9.txt");
 
@@ -13,61 +13,33 @@ fn main() -> anyhow::Result<()> {
         })
         .collect::<Result<Vec<_>, _>>()?;
 
-    let diff_trees = histories
-        .into_iter()
-        .map(|hist| {
-            let mut diff_tree = vec![hist.clone()];
-            loop {
-                let diffs = diff_tree
-                    .last()
-                    .unwrap()
-                    .windows(2)
-                    .map(|win| win[1] - win[0]);
-                if diffs.clone().all(|x| x == 0) {
-                    break;
-                }
-                diff_tree.push(diffs.collect_vec());
-            }
-            diff_tree
-        })
-        .collect_vec();
+    let polys = histories
+        .iter()
+        .map(|hist| Polynomial::fit(hist).unwrap())
+        .collect::<Vec<_>>();
 
-    println!("Part 1: {}", part1::solve(&diff_trees));
-    println!("Part 2: {}", part2::solve(&diff_trees));
+    let p1 = part1::solve(&polys);
+    assert_eq!(p1, 1798691765);
+    println!("Part 1: {p1}");
+    let p2 = part2::solve(&polys);
+    assert_eq!(p2, 1104);
+    println!("Part 2: {p2}");
 
     Ok(())
 }
 
 mod part1 {
-    pub(crate) fn solve(diff_trees: &[Vec<Vec<i64>>]) -> i64 {
-        let preds = diff_trees.iter().map(|diffs| {
-            let it = diffs.iter().rev();
-            let mut cur = 0;
-            let mut prev = 0;
-            for diff in it {
-                cur = diff.last().unwrap() + prev;
-                prev = cur;
-            }
-            cur
-        });
+    use aoc_2023::sequence::Polynomial;
 
-        preds.sum::<i64>()
+    pub(crate) fn solve(polys: &[Polynomial]) -> i128 {
+        polys.iter().map(|p| p.forward(1).unwrap()).sum()
     }
 }
 
 mod part2 {
-    pub(crate) fn solve(diff_trees: &[Vec<Vec<i64>>]) -> i64 {
-        let preds = diff_trees.iter().map(|diffs| {
-            let it = diffs.iter().rev();
-            let mut cur = 0;
-            let mut prev = 0;
-            for diff in it {
-                cur = diff.first().unwrap() - prev;
-                prev = cur;
-            }
-            cur
-        });
+    use aoc_2023::sequence::Polynomial;
 
-        preds.sum::<i64>()
+    pub(crate) fn solve(polys: &[Polynomial]) -> i128 {
+        polys.iter().map(|p| p.backward(1).unwrap()).sum()
     }
 }
@@ -0,0 +1,251 @@
+//! Beam propagation across grids of mirrors and splitters
+//!
+//! A beam enters a tile heading in some direction and leaves it in zero or more directions as
+//! determined by the tile's [`Optics`]. Runs of tiles that neither split nor stop the beam are
+//! traced once into segments which are then shared between all start positions.
+use crate::{Co2, Dir, Grid, GridBitSet};
+use rayon::prelude::*;
+use std::{collections::HashMap, sync::OnceLock};
+
+/// Outgoing directions encoded as a bitmask over `Dir::index`
+type DirMask = u8;
+
+/// Maps each tile character to the directions a beam leaves in, given the direction it came in
+#[derive(Debug, Clone, Default)]
+pub struct Optics {
+    tiles: HashMap<char, [DirMask; 4]>,
+}
+
+impl Optics {
+    /// Optics without any tiles
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Optics for empty space `.`, mirrors `/` and `\`, and splitters `|` and `-`
+    pub fn standard() -> Self {
+        use Dir as D;
+        Optics::new()
+            .with_tile('.', |dir| [dir])
+            .with_tile('/', |dir| {
+                [match dir {
+                    D::North => D::East,
+                    D::East => D::North,
+                    D::South => D::West,
+                    D::West => D::South,
+                }]
+            })
+            .with_tile('\\', |dir| {
+                [match dir {
+                    D::North => D::West,
+                    D::East => D::South,
+                    D::South => D::East,
+                    D::West => D::North,
+                }]
+            })
+            .with_tile('|', |dir| {
+                if dir.is_horizontal() {
+                    vec![D::North, D::South]
+                } else {
+                    vec![dir]
+                }
+            })
+            .with_tile('-', |dir| {
+                if dir.is_vertical() {
+                    vec![D::West, D::East]
+                } else {
+                    vec![dir]
+                }
+            })
+    }
+
+    /// Defines or replaces the behavior of `tile`
+    ///
+    /// `f` is given the direction of the incoming beam and returns the directions of the outgoing
+    /// beams. It is called once per direction when the tile is defined.
+    pub fn with_tile<I>(mut self, tile: char, f: impl Fn(Dir) -> I) -> Self
+    where
+        I: IntoIterator<Item = Dir>,
+    {
+        let masks = Dir::ALL.map(|dir| {
+            f(dir)
+                .into_iter()
+                .fold(0, |mask, out| mask | 1 << out.index())
+        });
+        self.tiles.insert(tile, masks);
+        self
+    }
+}
+
+/// Cells covered by a beam between two branching points, and the beams that continue from there
+#[derive(Debug)]
+struct Segment {
+    cells: Vec<u32>,
+    next: Vec<u32>,
+}
+
+/// Traces beams across a fixed grid, caching the segments found
+///
+/// Beam states are identified by `cell index * 4 + Dir::index`.
+pub struct BeamTracer {
+    rows: usize,
+    cols: usize,
+    tiles: Vec<[DirMask; 4]>,
+    segments: Vec<OnceLock<Segment>>,
+}
+
+impl BeamTracer {
+    /// Fails if `grid` contains a tile that is not defined in `optics`
+    pub fn new(grid: &Grid<char>, optics: &Optics) -> anyhow::Result<Self> {
+        let tiles = grid
+            .iter()
+            .map(|(co, c)| {
+                optics
+                    .tiles
+                    .get(c)
+                    .copied()
+                    .ok_or_else(|| anyhow::anyhow!("no optics for tile {c:?} at {co:?}"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(BeamTracer {
+            rows: grid.rows(),
+            cols: grid.cols(),
+            segments: (0..tiles.len() * 4).map(|_| OnceLock::new()).collect(),
+            tiles,
+        })
+    }
+
+    fn state(&self, co: Co2<usize>, dir: Dir) -> u32 {
+        ((co[0] * self.cols + co[1]) * 4 + dir.index()) as u32
+    }
+
+    /// Returns the state entering the neighbor of `cell` in direction `dir`, if any
+    fn step(&self, cell: usize, dir: Dir) -> Option<u32> {
+        let (row, col) = (cell / self.cols, cell % self.cols);
+        let (dr, dc) = dir.as_tuple();
+        let row = row.checked_add_signed(dr).filter(|&r| r < self.rows)?;
+        let col = col.checked_add_signed(dc).filter(|&c| c < self.cols)?;
+        Some(self.state(Co2(row, col), dir))
+    }
+
+    fn trace_segment(&self, state: u32) -> Segment {
+        let mut cells = vec![];
+        let mut state = state;
+
+        // A beam that has not branched by the time it has passed through every state must be
+        // looping without ever branching
+        for _ in 0..self.segments.len() {
+            let cell = state as usize / 4;
+            cells.push(cell as u32);
+
+            let out = self.tiles[cell][state as usize % 4];
+            let mut out_dirs = Dir::ALL
+                .into_iter()
+                .filter(|dir| out & 1 << dir.index() != 0);
+
+            if out.count_ones() == 1 {
+                match self.step(cell, out_dirs.next().unwrap()) {
+                    Some(nstate) => state = nstate,
+                    None => break,
+                }
+            } else {
+                let next = out_dirs.filter_map(|dir| self.step(cell, dir)).collect();
+                return Segment { cells, next };
+            }
+        }
+
+        Segment {
+            cells,
+            next: vec![],
+        }
+    }
+
+    /// Returns the set of energized cells for a beam entering `start` heading towards `dir`
+    pub fn energized_set(&self, start: Co2<usize>, dir: Dir) -> GridBitSet {
+        let mut energized = GridBitSet::new(self.rows, self.cols);
+        // Beam states, as the cell index and the direction entering it
+        let mut visited = GridBitSet::new(self.tiles.len(), 4);
+
+        let mut stack = vec![self.state(start, dir)];
+        while let Some(state) = stack.pop() {
+            if !visited.insert(Co2(state as usize / 4, state as usize % 4)) {
+                continue;
+            }
+
+            let segment = self.segments[state as usize].get_or_init(|| self.trace_segment(state));
+            for &cell in &segment.cells {
+                let cell = cell as usize;
+                energized.insert(Co2(cell / self.cols, cell % self.cols));
+            }
+            stack.extend(&segment.next);
+        }
+
+        energized
+    }
+
+    /// Returns the energized cells for a beam entering `start` heading towards `dir`
+    pub fn energized(&self, start: Co2<usize>, dir: Dir) -> Grid<bool> {
+        self.energized_set(start, dir).to_grid()
+    }
+
+    /// Counts the energized cells for a beam entering `start` heading towards `dir`
+    pub fn count_energized(&self, start: Co2<usize>, dir: Dir) -> usize {
+        self.energized_set(start, dir).count()
+    }
+
+    /// Returns every start state on the edges of the grid, heading inwards
+    pub fn edge_starts(&self) -> impl Iterator<Item = (Co2<usize>, Dir)> {
+        let (rows, cols) = (self.rows, self.cols);
+        (0..rows)
+            .map(|row| (Co2(row, 0), Dir::East))
+            .chain((0..rows).map(move |row| (Co2(row, cols - 1), Dir::West)))
+            .chain((0..cols).map(|col| (Co2(0, col), Dir::South)))
+            .chain((0..cols).map(move |col| (Co2(rows - 1, col), Dir::North)))
+    }
+
+    /// Returns the greatest number of cells energized from any of the edges, evaluated in
+    /// parallel
+    pub fn max_energized(&self) -> usize {
+        self.edge_starts()
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(|(co, dir)| self.count_energized(co, dir))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[test]
+fn beam_example() {
+    let grid = Grid::parse(
+        r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....",
+    );
+    let tracer = BeamTracer::new(&grid, &Optics::standard()).unwrap();
+
+    assert_eq!(tracer.count_energized(Co2(0, 0), Dir::East), 46);
+    assert_eq!(tracer.max_energized(), 51);
+}
+
+#[test]
+fn beam_custom_tile() {
+    // A tile that absorbs the beam
+    let optics = Optics::standard().with_tile('#', |_| []);
+    let tracer = BeamTracer::new(&Grid::parse("..#.."), &optics).unwrap();
+
+    assert_eq!(tracer.count_energized(Co2(0, 0), Dir::East), 3);
+    assert_eq!(
+        tracer.energized(Co2(0, 0), Dir::East).cells(),
+        [true, true, true, false, false]
+    );
+    assert!(BeamTracer::new(&Grid::parse("..?"), &optics).is_err());
+}
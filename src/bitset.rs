@@ -0,0 +1,141 @@
+use crate::{Co2, Grid};
+
+/// Set of cells within a fixed `rows` x `cols` area, stored as one bit per cell in row-major
+/// order
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GridBitSet {
+    rows: usize,
+    cols: usize,
+    words: Vec<u64>,
+}
+
+impl GridBitSet {
+    /// Creates an empty set for cells of a `rows` x `cols` area
+    pub fn new(rows: usize, cols: usize) -> Self {
+        GridBitSet {
+            rows,
+            cols,
+            words: vec![0; (rows * cols).div_ceil(64)],
+        }
+    }
+
+    /// Creates an empty set for the cells of `grid`
+    pub fn for_grid<T>(grid: &Grid<T>) -> Self {
+        Self::new(grid.rows(), grid.cols())
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn bit(&self, co: Co2<usize>) -> (usize, u64) {
+        assert!(
+            co[0] < self.rows && co[1] < self.cols,
+            "{co:?} is outside of a {}x{} bitset",
+            self.rows,
+            self.cols
+        );
+        let idx = co[0] * self.cols + co[1];
+        (idx / 64, 1 << (idx % 64))
+    }
+
+    /// Adds `co` to the set. Returns whether it was newly added.
+    ///
+    /// Panics if `co` is outside of the area.
+    pub fn insert(&mut self, co: Co2<usize>) -> bool {
+        let (word, mask) = self.bit(co);
+        let new = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        new
+    }
+
+    /// Removes `co` from the set. Returns whether it was present.
+    pub fn remove(&mut self, co: Co2<usize>) -> bool {
+        let (word, mask) = self.bit(co);
+        let present = self.words[word] & mask != 0;
+        self.words[word] &= !mask;
+        present
+    }
+
+    /// Returns true if `co` is in the set. Cells outside of the area are never in it.
+    pub fn contains(&self, co: Co2<usize>) -> bool {
+        co[0] < self.rows && co[1] < self.cols && {
+            let (word, mask) = self.bit(co);
+            self.words[word] & mask != 0
+        }
+    }
+
+    /// Counts the cells in the set
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Adds every cell of `other` to this set
+    ///
+    /// Panics if the sets cover areas of different sizes.
+    pub fn union(&mut self, other: &GridBitSet) {
+        assert_eq!(
+            (self.rows, self.cols),
+            (other.rows, other.cols),
+            "bitsets cover different areas"
+        );
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a |= b;
+        }
+    }
+
+    /// Returns an iterator across the cells in the set in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = Co2<usize>> + '_ {
+        self.words.iter().enumerate().flat_map(move |(idx, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                (word != 0).then(|| {
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    let cell = idx * 64 + bit;
+                    Co2(cell / self.cols, cell % self.cols)
+                })
+            })
+        })
+    }
+
+    /// Returns the set as a grid of flags
+    pub fn to_grid(&self) -> Grid<bool> {
+        Grid::from_fn(self.rows, self.cols, |co| self.contains(co))
+    }
+}
+
+#[test]
+fn grid_bitset_ops() {
+    let mut a = GridBitSet::new(3, 50);
+    assert!(a.insert(Co2(0, 1)));
+    assert!(!a.insert(Co2(0, 1)));
+    assert!(a.insert(Co2(2, 49)));
+    assert!(!a.contains(Co2(3, 0)));
+
+    let mut b = GridBitSet::new(3, 50);
+    b.insert(Co2(1, 30));
+    b.insert(Co2(0, 1));
+    a.union(&b);
+    assert_eq!(a.count(), 3);
+    assert_eq!(
+        a.iter().collect::<Vec<_>>(),
+        [Co2(0, 1), Co2(1, 30), Co2(2, 49)]
+    );
+
+    assert!(a.remove(Co2(0, 1)));
+    assert_eq!(a.count(), 2);
+    assert!(a.to_grid()[Co2(1, 30)]);
+}
@@ -0,0 +1,171 @@
+//! Ranking of card hands by the shape of their multiset, as in Camel Cards
+//!
+//! A hand's shape is the sizes of its groups of equal cards, largest first, e.g., `[3, 2]` for a
+//! full house. Comparing shapes lexicographically orders the hands exactly like poker kinds do.
+//! Ties are broken card by card using a configurable card order.
+
+/// Named kinds of five-card hands, weakest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Kind {
+    High,
+    OnePair,
+    TwoPair,
+    Three,
+    House,
+    Four,
+    Five,
+}
+
+impl Kind {
+    /// Returns the kind for the shape of a five-card hand
+    pub fn from_shape(shape: &[u8]) -> Option<Kind> {
+        Some(match shape {
+            [5] => Kind::Five,
+            [4, 1] => Kind::Four,
+            [3, 2] => Kind::House,
+            [3, 1, 1] => Kind::Three,
+            [2, 2, 1] => Kind::TwoPair,
+            [2, 1, 1, 1] => Kind::OnePair,
+            [1, 1, 1, 1, 1] => Kind::High,
+            _ => return None,
+        })
+    }
+}
+
+/// Card ordering and wildcards that determine how hands are ranked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    /// Cards from weakest to strongest
+    order: Vec<char>,
+    wildcards: Vec<char>,
+}
+
+impl Rules {
+    /// Rules with the cards in `order` from weakest to strongest and no wildcards
+    pub fn new(order: &str) -> Self {
+        Rules {
+            order: order.chars().collect(),
+            wildcards: vec![],
+        }
+    }
+
+    /// `2` is weakest and `A` is strongest
+    pub fn standard() -> Self {
+        Self::new("23456789TJQKA")
+    }
+
+    /// Like standard rules except `J` is the weakest card and acts as a wildcard
+    pub fn jokers() -> Self {
+        Self::new("J23456789TQKA").with_wildcards("J")
+    }
+
+    /// Makes each of `wildcards` stand in for whichever card makes the strongest hand
+    ///
+    /// Wildcards still use their place in the card order for breaking ties.
+    pub fn with_wildcards(mut self, wildcards: &str) -> Self {
+        self.wildcards = wildcards.chars().collect();
+        self
+    }
+
+    /// Returns the strength of `card`, or `None` if it's not part of the card order
+    pub fn rank(&self, card: char) -> Option<u8> {
+        self.order.iter().position(|&c| c == card).map(|r| r as u8)
+    }
+
+    pub fn is_wild(&self, card: char) -> bool {
+        self.wildcards.contains(&card)
+    }
+
+    /// Returns the best shape `cards` can take with the wildcards assigned
+    ///
+    /// Adding all wildcards to the largest group is optimal, since shapes are compared
+    /// lexicographically.
+    pub fn shape(&self, cards: &[char]) -> Vec<u8> {
+        let mut counts: Vec<(char, u8)> = vec![];
+        let mut wild = 0;
+        for &card in cards {
+            if self.is_wild(card) {
+                wild += 1;
+            } else if let Some((_, n)) = counts.iter_mut().find(|(c, _)| *c == card) {
+                *n += 1;
+            } else {
+                counts.push((card, 1));
+            }
+        }
+
+        let mut shape = counts.into_iter().map(|(_, n)| n).collect::<Vec<_>>();
+        shape.sort_unstable_by(|a, b| b.cmp(a));
+        match shape.first_mut() {
+            Some(largest) => *largest += wild,
+            None if wild != 0 => shape.push(wild),
+            None => {}
+        }
+
+        shape
+    }
+
+    /// Classifies `cards` into a hand, or returns `None` if it contains an unknown card
+    pub fn hand(&self, cards: &str) -> Option<Hand> {
+        let cards = cards.chars().collect::<Vec<_>>();
+        let ranks = cards
+            .iter()
+            .map(|&c| self.rank(c))
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Hand {
+            shape: self.shape(&cards),
+            ranks,
+            cards,
+        })
+    }
+}
+
+/// A classified hand, ordered from weakest to strongest
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hand {
+    // Field order determines the derived ordering
+    shape: Vec<u8>,
+    ranks: Vec<u8>,
+    cards: Vec<char>,
+}
+
+impl Hand {
+    pub fn shape(&self) -> &[u8] {
+        &self.shape
+    }
+
+    /// Returns the named kind of the hand if it has five cards
+    pub fn kind(&self) -> Option<Kind> {
+        Kind::from_shape(&self.shape)
+    }
+
+    pub fn cards(&self) -> &[char] {
+        &self.cards
+    }
+}
+
+#[test]
+fn cards_ranking() {
+    let rules = Rules::standard();
+    let hands = ["32T3K", "T55J5", "KK677", "KTJJT", "QQQJA"].map(|h| rules.hand(h).unwrap());
+    let kinds = hands.iter().map(|h| h.kind().unwrap()).collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [
+            Kind::OnePair,
+            Kind::Three,
+            Kind::TwoPair,
+            Kind::TwoPair,
+            Kind::Three
+        ]
+    );
+    assert!(hands[2] > hands[3]);
+    assert!(hands[4] > hands[1]);
+
+    let rules = Rules::jokers();
+    let hand = |h| rules.hand(h).unwrap();
+    assert_eq!(hand("KTJJT").kind(), Some(Kind::Four));
+    assert_eq!(hand("JJJJJ").kind(), Some(Kind::Five));
+    assert!(hand("QQQQ2") > hand("JKKK2"));
+    assert_eq!(rules.hand("XYZ"), None);
+}
@@ -0,0 +1,284 @@
+use std::{fmt, num::TryFromIntError, ops};
+
+/// Coordinate or vector of `N` values
+///
+/// Components are accessed by index: `co[0]`, `co[1]`, ... Arithmetic is component-wise.
+///
+/// `Co2` and `Co3` are functions rather than tuple structs, so they cannot be used in patterns.
+/// Destructure the array instead, as in `let Co([row, col]) = co` or
+/// `let [row, col] = co.into_array()`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Co<T, const N: usize>(pub [T; N]);
+
+/// 2D coordinate, (row, col) when indexing grids
+pub type Co2<T> = Co<T, 2>;
+
+/// 3D coordinate
+pub type Co3<T> = Co<T, 3>;
+
+/// Creates a 2D coordinate
+#[allow(non_snake_case)]
+pub const fn Co2<T>(a: T, b: T) -> Co2<T> {
+    Co([a, b])
+}
+
+/// Creates a 3D coordinate
+#[allow(non_snake_case)]
+pub const fn Co3<T>(a: T, b: T, c: T) -> Co3<T> {
+    Co([a, b, c])
+}
+
+impl<T, const N: usize> Co<T, N> {
+    pub const fn new(values: [T; N]) -> Self {
+        Co(values)
+    }
+
+    pub fn as_array(&self) -> &[T; N] {
+        &self.0
+    }
+
+    pub fn into_array(self) -> [T; N] {
+        self.0
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Co<U, N> {
+        Co(self.0.map(f))
+    }
+
+    /// Converts each component, failing if any of them does not fit in `U`
+    pub fn try_cast<U: TryFrom<T>>(self) -> Result<Co<U, N>, U::Error> {
+        let mut err = None;
+        let values = self
+            .0
+            .map(|v| U::try_from(v).map_err(|e| err = Some(e)).ok());
+        match err {
+            Some(e) => Err(e),
+            None => Ok(Co(values.map(Option::unwrap))),
+        }
+    }
+
+    /// Converts each component losslessly
+    pub fn cast<U: From<T>>(self) -> Co<U, N> {
+        self.map(U::from)
+    }
+
+    fn zip_with<U, V>(self, rhs: Co<U, N>, mut f: impl FnMut(T, U) -> V) -> Co<V, N> {
+        let mut rhs = rhs.0.into_iter();
+        self.map(|a| f(a, rhs.next().unwrap()))
+    }
+}
+
+impl<T: Difference, const N: usize> Co<T, N> {
+    /// Moves by a signed offset, returning `None` if any component would not fit in `T`
+    pub fn checked_offset(self, delta: Co<T::Signed, N>) -> Option<Self> {
+        let mut fits = true;
+        let moved = self.zip_with(delta, |a, d| {
+            a.checked_offset(d).unwrap_or_else(|| {
+                fits = false;
+                a
+            })
+        });
+        fits.then_some(moved)
+    }
+}
+
+impl<T: Copy> Co<T, 2> {
+    pub fn as_tuple(&self) -> (T, T) {
+        (self.0[0], self.0[1])
+    }
+}
+
+impl<T: Copy> Co<T, 3> {
+    pub fn as_tuple(&self) -> (T, T, T) {
+        (self.0[0], self.0[1], self.0[2])
+    }
+}
+
+impl<T, const N: usize> ops::Index<usize> for Co<T, N> {
+    type Output = T;
+
+    fn index(&self, axis: usize) -> &Self::Output {
+        &self.0[axis]
+    }
+}
+
+impl<T, const N: usize> ops::IndexMut<usize> for Co<T, N> {
+    fn index_mut(&mut self, axis: usize) -> &mut Self::Output {
+        &mut self.0[axis]
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Co<T, N> {
+    fn from(value: [T; N]) -> Self {
+        Co(value)
+    }
+}
+
+impl<T> From<(T, T)> for Co<T, 2> {
+    fn from(value: (T, T)) -> Self {
+        Co([value.0, value.1])
+    }
+}
+
+impl<T> From<(T, T, T)> for Co<T, 3> {
+    fn from(value: (T, T, T)) -> Self {
+        Co([value.0, value.1, value.2])
+    }
+}
+
+impl<T: ops::Add<Output = T>, const N: usize> ops::Add for Co<T, N> {
+    type Output = Co<T, N>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a + b)
+    }
+}
+
+impl<T: ops::AddAssign, const N: usize> ops::AddAssign for Co<T, N> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (a, b) in self.0.iter_mut().zip(rhs.0) {
+            *a += b;
+        }
+    }
+}
+
+impl<T: ops::Neg<Output = T>, const N: usize> ops::Neg for Co<T, N> {
+    type Output = Co<T, N>;
+
+    fn neg(self) -> Self::Output {
+        self.map(|a| -a)
+    }
+}
+
+/// Scales every component by `rhs`
+impl<T: ops::Mul<Output = T> + Copy, const N: usize> ops::Mul<T> for Co<T, N> {
+    type Output = Co<T, N>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        self.map(|a| a * rhs)
+    }
+}
+
+/// Component type whose differences are taken in a signed type
+pub trait Difference: Copy {
+    type Signed;
+
+    fn difference(self, rhs: Self) -> Self::Signed;
+
+    /// Adds a difference back, returning `None` if the result does not fit
+    fn checked_offset(self, delta: Self::Signed) -> Option<Self>;
+}
+
+/// Subtracts component-wise. Unsigned components produce signed differences, so that e.g. two
+/// grid positions can be subtracted in any order.
+impl<T: Difference, const N: usize> ops::Sub for Co<T, N> {
+    type Output = Co<T::Signed, N>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, T::difference)
+    }
+}
+
+macro_rules! impl_signed_pair {
+    ($($signed:ty => $unsigned:ty),*) => {$(
+        impl Difference for $signed {
+            type Signed = $signed;
+
+            fn difference(self, rhs: Self) -> Self::Signed {
+                self - rhs
+            }
+
+            fn checked_offset(self, delta: Self::Signed) -> Option<Self> {
+                self.checked_add(delta)
+            }
+        }
+
+        impl Difference for $unsigned {
+            type Signed = $signed;
+
+            fn difference(self, rhs: Self) -> Self::Signed {
+                self as $signed - rhs as $signed
+            }
+
+            fn checked_offset(self, delta: Self::Signed) -> Option<Self> {
+                self.checked_add_signed(delta)
+            }
+        }
+
+        impl<const N: usize> TryFrom<Co<$signed, N>> for Co<$unsigned, N> {
+            type Error = TryFromIntError;
+
+            fn try_from(value: Co<$signed, N>) -> Result<Self, Self::Error> {
+                value.try_cast()
+            }
+        }
+
+        impl TryFrom<($signed, $signed)> for Co<$unsigned, 2> {
+            type Error = TryFromIntError;
+
+            fn try_from(value: ($signed, $signed)) -> Result<Self, Self::Error> {
+                Co2::from(value).try_cast()
+            }
+        }
+
+        impl TryFrom<($signed, $signed, $signed)> for Co<$unsigned, 3> {
+            type Error = TryFromIntError;
+
+            fn try_from(value: ($signed, $signed, $signed)) -> Result<Self, Self::Error> {
+                Co3::from(value).try_cast()
+            }
+        }
+    )*};
+}
+
+impl_signed_pair!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, isize => usize);
+
+/// Formats as `Co2(1, 2)`, like a tuple struct named after the dimension
+impl<T: fmt::Debug, const N: usize> fmt::Debug for Co<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut tuple = f.debug_tuple(&format!("Co{N}"));
+        for v in &self.0 {
+            tuple.field(v);
+        }
+        tuple.finish()
+    }
+}
+
+/// Formats as `(1, 2)`
+impl<T: fmt::Display, const N: usize> fmt::Display for Co<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        for (i, v) in self.0.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            v.fmt(f)?;
+        }
+        write!(f, ")")
+    }
+}
+
+#[test]
+fn co_ops() {
+    let a = Co3::from((1, 2, 3));
+    let b = Co3::from((1, 2, 3));
+    assert_eq!(a + b, Co3(2, 4, 6));
+    assert_eq!(-a * 2, Co3(-2, -4, -6));
+
+    let Co([x, y, z]) = a;
+    assert_eq!((x, y, z), (1, 2, 3));
+
+    let (p, q) = (Co2(1usize, 5usize), Co2(3usize, 2usize));
+    assert_eq!(p - q, Co2(-2isize, 3));
+    assert_eq!(
+        Co2::<usize>::try_from(p - q),
+        Err(usize::try_from(-1).unwrap_err())
+    );
+    assert_eq!(Co2::<usize>::try_from((3isize, 4isize)), Ok(Co2(3, 4)));
+    assert_eq!(q.checked_offset(p - q), Some(p));
+    assert_eq!(q.checked_offset(Co2(-4, 0)), None);
+    assert_eq!(Co2(1u32, 5).cast::<u64>(), Co2(1u64, 5));
+    assert!(Co2(0, 9) < Co2(1, 0));
+
+    assert_eq!(format!("{p} {p:?}"), "(1, 5) Co2(1, 5)");
+}
@@ -0,0 +1,40 @@
+/// Returns (g, x, y) such that a * x + b * y = g = gcd(a, b)
+pub fn ext_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a.abs(), a.signum(), 0)
+    } else {
+        let (g, x, y) = ext_gcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+
+/// Solves a system of congruences x = residue (mod modulus) whose moduli need not be coprime
+///
+/// Returns `(x, m)` where `x` is the smallest non-negative solution and every solution is `x`
+/// plus a multiple of `m`, the least common multiple of the moduli. Returns `None` if the
+/// congruences contradict each other.
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    congruences.iter().try_fold((0, 1), |(a1, m1), &(a2, m2)| {
+        assert!(m2 > 0, "modulus must be positive");
+        let a2 = a2.rem_euclid(m2);
+
+        let (g, p, _) = ext_gcd(m1, m2);
+        if (a2 - a1) % g != 0 {
+            return None;
+        }
+
+        // m1 * p = g (mod m2), so stepping a1 by m1 * k reaches a2 when k = (a2 - a1) / g * p
+        let m2g = m2 / g;
+        let k = ((a2 - a1) / g).rem_euclid(m2g) * p.rem_euclid(m2g) % m2g;
+        let lcm = m1 * m2g;
+        Some(((a1 + m1 * k).rem_euclid(lcm), lcm))
+    })
+}
+
+#[test]
+fn crt_non_coprime() {
+    assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+    assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+    assert_eq!(crt(&[]), Some((0, 1)));
+}
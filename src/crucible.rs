@@ -0,0 +1,154 @@
+//! Shortest paths across weighted grids for movers that must go straight for a while
+//!
+//! The search state is the position, the heading and how many steps have been taken in that
+//! heading. Entering a cell costs its weight. Since the weights are small integers, the states
+//! are settled with Dial's algorithm: a ring of buckets indexed by distance replaces the binary
+//! heap.
+use crate::{Co2, Dir, Grid};
+
+/// Limits on how the mover may run straight and turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunRules {
+    /// Steps in one heading before the mover may turn or stop
+    pub min_run: usize,
+    /// Steps in one heading after which the mover must turn
+    pub max_run: usize,
+    /// Whether the mover may turn around
+    pub reverse: bool,
+}
+
+impl RunRules {
+    /// At most three steps in one direction, no reversing
+    pub fn crucible() -> Self {
+        RunRules {
+            min_run: 1,
+            max_run: 3,
+            reverse: false,
+        }
+    }
+
+    /// At least four and at most ten steps in one direction, no reversing
+    pub fn ultra_crucible() -> Self {
+        RunRules {
+            min_run: 4,
+            max_run: 10,
+            reverse: false,
+        }
+    }
+
+    fn can_turn(&self, run: usize) -> bool {
+        run >= self.min_run
+    }
+}
+
+/// Returns the least total weight of the cells entered on the way from `start` to `goal`
+///
+/// The mover may start in any heading and must have run at least `min_run` steps when it stops
+/// at the goal. Returns `None` if the goal cannot be reached, or if either end lies outside of
+/// the grid.
+pub fn min_path_cost(
+    weights: &Grid<u8>,
+    start: Co2<usize>,
+    goal: Co2<usize>,
+    rules: RunRules,
+) -> Option<u32> {
+    assert!(rules.max_run >= rules.min_run.max(1), "invalid run rules");
+    if !weights.contains(start) || !weights.contains(goal) {
+        return None;
+    }
+    if start == goal {
+        return Some(0);
+    }
+
+    // State index: (cell * 4 + heading) * (max_run + 1) + run
+    let runs = rules.max_run + 1;
+    let state = |co: Co2<usize>, dir: Dir, run: usize| {
+        (weights.index_of(co) * 4 + dir.index()) * runs + run
+    };
+
+    let ring = *weights.cells().iter().max()? as usize + 1;
+    let mut buckets: Vec<Vec<usize>> = vec![vec![]; ring];
+    let mut dist = vec![u32::MAX; weights.len() * 4 * runs];
+
+    // Steps from `co` towards `dir`, queueing the new state if it improves on the best known
+    let relax = |buckets: &mut [Vec<usize>], dist: &mut [u32], co, dir, run, d: u32| {
+        if let Some(nco) = weights.step(co, dir) {
+            let nd = d + weights[nco] as u32;
+            let ns = state(nco, dir, run);
+            if nd < dist[ns] {
+                dist[ns] = nd;
+                buckets[nd as usize % ring].push(ns);
+            }
+        }
+    };
+
+    for dir in Dir::ALL {
+        relax(&mut buckets, &mut dist, start, dir, 1, 0);
+    }
+
+    let mut cur = 0u32;
+    loop {
+        let bucket = cur as usize % ring;
+        if buckets.iter().all(Vec::is_empty) {
+            return None;
+        }
+        while let Some(s) = buckets[bucket].pop() {
+            // Skip entries that were improved on after being queued
+            if dist[s] != cur {
+                continue;
+            }
+
+            let run = s % runs;
+            let dir = Dir::ALL[s / runs % 4];
+            let co = weights.co_of(s / runs / 4);
+            if co == goal && run >= rules.min_run {
+                return Some(cur);
+            }
+
+            if run < rules.max_run {
+                relax(&mut buckets, &mut dist, co, dir, run + 1, cur);
+            }
+            if rules.can_turn(run) {
+                let turns = [dir.turn_left(), dir.turn_right(), dir.reverse()];
+                for ndir in turns.into_iter().take(if rules.reverse { 3 } else { 2 }) {
+                    relax(&mut buckets, &mut dist, co, ndir, 1, cur);
+                }
+            }
+        }
+        cur += 1;
+    }
+}
+
+#[test]
+fn crucible_example() {
+    let weights = Grid::parse(
+        "2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533",
+    )
+    .map(|c| c.to_digit(10).unwrap() as u8);
+    let goal = Co2(weights.rows() - 1, weights.cols() - 1);
+
+    assert_eq!(
+        min_path_cost(&weights, Co2(0, 0), goal, RunRules::crucible()),
+        Some(102)
+    );
+    assert_eq!(
+        min_path_cost(&weights, Co2(0, 0), goal, RunRules::ultra_crucible()),
+        Some(94)
+    );
+    assert_eq!(
+        min_path_cost(&weights, Co2(0, 13), goal, RunRules::crucible()),
+        None
+    );
+}
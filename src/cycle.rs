@@ -0,0 +1,119 @@
+use crate::crt;
+use itertools::Itertools;
+use std::{collections::HashMap, hash::Hash};
+
+/// Eventually periodic behavior of a deterministic walk and the steps at which it hits a target
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalkCycle {
+    /// Number of steps before the walk enters its cycle
+    pub prefix: u64,
+    /// Length of the cycle
+    pub period: u64,
+    /// Steps before the cycle at which the walk is on a target
+    pub prefix_hits: Vec<u64>,
+    /// Steps within the first pass of the cycle, `prefix..prefix + period`, at which the walk is
+    /// on a target. Each repeats every `period` steps.
+    pub cycle_hits: Vec<u64>,
+}
+
+impl WalkCycle {
+    /// Follows a walk where each step depends on the node and on an instruction index that
+    /// cycles through `0..instr_len`, until a (node, instruction index) state repeats
+    ///
+    /// Panics if `instr_len` is zero.
+    pub fn analyze<N>(
+        start: N,
+        instr_len: usize,
+        mut step: impl FnMut(&N, usize) -> N,
+        is_hit: impl Fn(&N) -> bool,
+    ) -> Self
+    where
+        N: Hash + Eq + Clone,
+    {
+        assert!(instr_len != 0, "walk needs at least one instruction");
+        let mut seen = HashMap::new();
+        let mut hits = vec![];
+        let mut node = start;
+
+        let mut t = 0;
+        loop {
+            let instr = (t % instr_len as u64) as usize;
+            if let Some(&first) = seen.get(&(node.clone(), instr)) {
+                let (prefix_hits, cycle_hits) = hits.into_iter().partition(|&h| h < first);
+                return WalkCycle {
+                    prefix: first,
+                    period: t - first,
+                    prefix_hits,
+                    cycle_hits,
+                };
+            }
+            if is_hit(&node) {
+                hits.push(t);
+            }
+            let next = step(&node, instr);
+            seen.insert((node, instr), t);
+            node = next;
+            t += 1;
+        }
+    }
+
+    /// Returns true if the walk is on a target after `t` steps
+    pub fn hits_at(&self, t: u64) -> bool {
+        if t < self.prefix {
+            self.prefix_hits.contains(&t)
+        } else {
+            let offset = (t - self.prefix) % self.period;
+            self.cycle_hits.contains(&(self.prefix + offset))
+        }
+    }
+}
+
+/// Returns the first step at which all walks are on a target simultaneously
+///
+/// Steps before some walk has entered its cycle are checked directly. Beyond that, each
+/// combination of cycle hits is solved as a system of congruences, so the walks need not have
+/// their hits at offset zero of their cycles.
+pub fn first_common_hit(walks: &[WalkCycle]) -> Option<u64> {
+    let direct = walks
+        .iter()
+        .flat_map(|w| w.prefix_hits.iter().copied())
+        .filter(|&t| walks.iter().all(|w| w.hits_at(t)))
+        .min();
+
+    let periodic = walks
+        .iter()
+        .map(|w| w.cycle_hits.iter().map(move |&h| (h, w.period)))
+        .multi_cartesian_product()
+        .filter_map(|combo| {
+            let congruences = combo
+                .iter()
+                .map(|&(h, period)| (h as i128, period as i128))
+                .collect_vec();
+            let (x, m) = crt(&congruences)?;
+
+            // Smallest solution at which every walk is already within its cycle
+            let lo = combo.iter().map(|&(h, _)| h as i128).max()?;
+            Some((lo + (x - lo).rem_euclid(m)) as u64)
+        })
+        .min();
+
+    direct.into_iter().chain(periodic).min()
+}
+
+#[test]
+fn common_hit_with_offsets() {
+    // 0 -> 1 -> 2 -> 3 -> 1 ..., hit on 3 at steps 3, 6, 9, ...
+    let a = WalkCycle::analyze(0u32, 1, |&n, _| if n == 3 { 1 } else { n + 1 }, |&n| n == 3);
+    assert_eq!(
+        (a.prefix, a.period, a.cycle_hits.as_slice()),
+        (1, 3, &[3][..])
+    );
+
+    // 0 -> 1 -> 0 ..., hit on 1 at steps 1, 3, 5, ...
+    let b = WalkCycle::analyze(0u32, 1, |&n, _| 1 - n, |&n| n == 1);
+    assert_eq!(first_common_hit(&[a.clone(), b.clone()]), Some(3));
+
+    // Hits only at even steps can never coincide with b
+    let c = WalkCycle::analyze(0u32, 1, |&n, _| 1 - n, |&n| n == 0);
+    assert_eq!(first_common_hit(&[b, c]), None);
+}
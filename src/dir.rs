@@ -0,0 +1,48 @@
+/// Cardinal direction on a (row, col) grid where north is towards row zero
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Dir {
+    /// All directions, clockwise starting from north
+    pub const ALL: [Dir; 4] = [Dir::North, Dir::East, Dir::South, Dir::West];
+
+    /// Returns the (row, col) offset of a single step in this direction
+    pub fn as_tuple(&self) -> (isize, isize) {
+        match self {
+            Dir::North => (-1, 0),
+            Dir::East => (0, 1),
+            Dir::South => (1, 0),
+            Dir::West => (0, -1),
+        }
+    }
+
+    /// Returns the position of this direction in `Dir::ALL`
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        *self == Dir::East || *self == Dir::West
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        *self == Dir::North || *self == Dir::South
+    }
+
+    pub fn reverse(&self) -> Dir {
+        Dir::ALL[(self.index() + 2) % 4]
+    }
+
+    pub fn turn_left(&self) -> Dir {
+        Dir::ALL[(self.index() + 3) % 4]
+    }
+
+    pub fn turn_right(&self) -> Dir {
+        Dir::ALL[(self.index() + 1) % 4]
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{neighbors, Bounds, Co2, Connectivity, Grid};
+use std::collections::VecDeque;
+
+/// Brightness ramp of `Grid::heat_map`, from near to far
+const HEAT_RAMP: &[char] = &['@', '%', '#', '*', '+', '=', '-', ':', '.'];
+
+/// Returns the number of orthogonal steps from the nearest of `sources` to each cell, moving only
+/// through cells for which `passable` returns true. Unreachable cells are `None`.
+///
+/// Sources are at distance zero whether or not they are passable.
+pub fn bfs_distances<T>(
+    grid: &Grid<T>,
+    sources: impl IntoIterator<Item = Co2<usize>>,
+    passable: impl Fn(Co2<usize>, &T) -> bool,
+) -> Grid<Option<u32>> {
+    let mut dist = Grid::new(grid.rows(), grid.cols(), None);
+    let mut queue = VecDeque::new();
+    for source in sources {
+        if dist[source].is_none() {
+            dist[source] = Some(0);
+            queue.push_back(source);
+        }
+    }
+
+    let bounds = Bounds::Clipped(Co2(grid.rows(), grid.cols()));
+    while let Some(co) = queue.pop_front() {
+        let next_dist = dist[co].map(|d| d + 1);
+        for (_, next) in neighbors(co, Connectivity::Orthogonal, bounds) {
+            if dist[next].is_none() && passable(next, &grid[next]) {
+                dist[next] = next_dist;
+                queue.push_back(next);
+            }
+        }
+    }
+    dist
+}
+
+impl Grid<Option<u32>> {
+    /// Counts the cells that can be reached in exactly `n` steps, allowing steps back and forth
+    ///
+    /// On a grid, a cell can be revisited only after an even number of steps, so these are the
+    /// cells within `n` steps whose distance has the same parity as `n`.
+    pub fn reachable_in_exactly(&self, n: u32) -> usize {
+        self.cells()
+            .iter()
+            .flatten()
+            .filter(|&&d| d <= n && d % 2 == n % 2)
+            .count()
+    }
+
+    /// Returns the number of cells at each distance, indexed by distance
+    pub fn distance_histogram(&self) -> Vec<usize> {
+        let len = self
+            .cells()
+            .iter()
+            .flatten()
+            .max()
+            .map_or(0, |&d| d as usize + 1);
+        let mut histogram = vec![0; len];
+        for &d in self.cells().iter().flatten() {
+            histogram[d as usize] += 1;
+        }
+        histogram
+    }
+
+    /// Draws the distances with characters getting lighter farther away, leaving unreachable
+    /// cells blank
+    pub fn heat_map(&self) -> Grid<char> {
+        let max = self.cells().iter().flatten().max().copied().unwrap_or(0);
+        self.map(|d| match *d {
+            Some(d) => HEAT_RAMP[d as usize * (HEAT_RAMP.len() - 1) / max.max(1) as usize],
+            None => ' ',
+        })
+    }
+}
+
+#[test]
+fn bfs_reachability() {
+    let grid = Grid::parse(
+        "...........\n\
+         .....###.#.\n\
+         .###.##..#.\n\
+         ..#.#...#..\n\
+         ....#.#....\n\
+         .##..S####.\n\
+         .##..#...#.\n\
+         .......##..\n\
+         .##.#.####.\n\
+         .##..##.##.\n\
+         ...........",
+    );
+    let start = grid.find(&'S').unwrap();
+    let dist = bfs_distances(&grid, [start], |_, &c| c != '#');
+    assert_eq!(dist.reachable_in_exactly(6), 16);
+    assert_eq!(dist[Co2(1, 5)], None);
+    assert_eq!(dist.distance_histogram()[..3], [1, 2, 3]);
+    assert_eq!(dist.heat_map()[start], '@');
+
+    let two = bfs_distances(&grid, [Co2(0, 0), Co2(0, 10)], |_, &c| c != '#');
+    assert_eq!(two[Co2(0, 5)], Some(5));
+}
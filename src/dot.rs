@@ -0,0 +1,242 @@
+//! Graphviz DOT export for debugging puzzle graphs
+use petgraph::visit::{
+    EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeReferences, NodeIndexable, NodeRef,
+};
+use std::{fmt, io};
+
+/// Node shape as understood by Graphviz
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Shape {
+    #[default]
+    Ellipse,
+    Box,
+    Circle,
+    DoubleCircle,
+    Diamond,
+    Octagon,
+    Triangle,
+    InvTriangle,
+    House,
+    Plaintext,
+}
+
+impl Shape {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Shape::Ellipse => "ellipse",
+            Shape::Box => "box",
+            Shape::Circle => "circle",
+            Shape::DoubleCircle => "doublecircle",
+            Shape::Diamond => "diamond",
+            Shape::Octagon => "octagon",
+            Shape::Triangle => "triangle",
+            Shape::InvTriangle => "invtriangle",
+            Shape::House => "house",
+            Shape::Plaintext => "plaintext",
+        }
+    }
+}
+
+/// How a single node is drawn
+#[derive(Debug, Clone, Default)]
+pub struct NodeStyle {
+    /// Label to draw instead of the node ID
+    pub label: Option<String>,
+    pub shape: Shape,
+}
+
+impl NodeStyle {
+    pub fn new(shape: Shape) -> Self {
+        NodeStyle { label: None, shape }
+    }
+
+    pub fn with_label(mut self, label: impl fmt::Display) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DotNode {
+    id: String,
+    style: NodeStyle,
+}
+
+#[derive(Debug, Clone)]
+struct DotEdge {
+    from: String,
+    to: String,
+    label: Option<String>,
+}
+
+/// A graph that can be written out in the DOT language
+///
+/// Nodes are identified by their `Display` representation. Edges may refer to nodes that were
+/// never added explicitly, in which case Graphviz draws them with the default style.
+#[derive(Debug, Clone)]
+pub struct DotGraph {
+    name: String,
+    directed: bool,
+    nodes: Vec<DotNode>,
+    edges: Vec<DotEdge>,
+}
+
+impl DotGraph {
+    /// Creates an empty directed graph
+    pub fn digraph(name: impl Into<String>) -> Self {
+        DotGraph {
+            name: name.into(),
+            directed: true,
+            nodes: vec![],
+            edges: vec![],
+        }
+    }
+
+    /// Creates an empty undirected graph
+    pub fn graph(name: impl Into<String>) -> Self {
+        DotGraph {
+            directed: false,
+            ..Self::digraph(name)
+        }
+    }
+
+    pub fn node(&mut self, id: impl fmt::Display, style: NodeStyle) -> &mut Self {
+        self.nodes.push(DotNode {
+            id: id.to_string(),
+            style,
+        });
+        self
+    }
+
+    pub fn edge(&mut self, from: impl fmt::Display, to: impl fmt::Display) -> &mut Self {
+        self.edges.push(DotEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            label: None,
+        });
+        self
+    }
+
+    /// Adds an edge annotated with a label, e.g., its weight
+    pub fn labeled_edge(
+        &mut self,
+        from: impl fmt::Display,
+        to: impl fmt::Display,
+        label: impl fmt::Display,
+    ) -> &mut Self {
+        self.edges.push(DotEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            label: Some(label.to_string()),
+        });
+        self
+    }
+
+    /// Converts any `petgraph` graph into a `DotGraph`
+    ///
+    /// Nodes are identified by their index in `graph`. `node_style` determines how each node is
+    /// drawn and `edge_label` what is written on each edge, if anything.
+    pub fn from_petgraph<G>(
+        name: impl Into<String>,
+        graph: G,
+        node_style: impl Fn(&G::NodeWeight) -> NodeStyle,
+        edge_label: impl Fn(&G::EdgeWeight) -> Option<String>,
+    ) -> Self
+    where
+        G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+    {
+        let mut dot = if graph.is_directed() {
+            Self::digraph(name)
+        } else {
+            Self::graph(name)
+        };
+
+        for node in graph.node_references() {
+            dot.node(graph.to_index(node.id()), node_style(node.weight()));
+        }
+        for edge in graph.edge_references() {
+            let from = graph.to_index(edge.source());
+            let to = graph.to_index(edge.target());
+            match edge_label(edge.weight()) {
+                Some(label) => dot.labeled_edge(from, to, label),
+                None => dot.edge(from, to),
+            };
+        }
+
+        dot
+    }
+
+    pub fn write<W: io::Write>(&self, mut w: W) -> io::Result<()> {
+        write!(w, "{self}")
+    }
+}
+
+/// Returns true if the program was invoked with the `graph` argument, as done by `just graph IDX`
+pub fn requested() -> bool {
+    std::env::args().skip(1).any(|arg| arg == "graph")
+}
+
+/// Quotes `s` as a DOT ID
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl fmt::Display for DotGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (kind, arrow) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+
+        writeln!(f, "{kind} {} {{", quote(&self.name))?;
+        for DotNode { id, style } in &self.nodes {
+            write!(f, "    {} [shape={}", quote(id), style.shape.as_str())?;
+            if let Some(label) = &style.label {
+                write!(f, ", label={}", quote(label))?;
+            }
+            writeln!(f, "];")?;
+        }
+        for DotEdge { from, to, label } in &self.edges {
+            write!(f, "    {} {arrow} {}", quote(from), quote(to))?;
+            if let Some(label) = label {
+                write!(f, " [label={}]", quote(label))?;
+            }
+            writeln!(f, ";")?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+#[test]
+fn dot_digraph() {
+    let mut dot = DotGraph::digraph("g");
+    dot.node("a", NodeStyle::new(Shape::Box).with_label("start"))
+        .node("b", NodeStyle::default())
+        .labeled_edge("a", "b", 3);
+
+    assert_eq!(
+        dot.to_string(),
+        "digraph \"g\" {\n    \"a\" [shape=box, label=\"start\"];\n    \"b\" [shape=ellipse];\n    \"a\" -> \"b\" [label=\"3\"];\n}\n"
+    );
+}
+
+#[test]
+fn dot_from_petgraph() {
+    let mut graph = petgraph::graph::UnGraph::<&str, usize>::new_undirected();
+    let a = graph.add_node("a");
+    let b = graph.add_node("b\"");
+    graph.add_edge(a, b, 7);
+
+    let dot = DotGraph::from_petgraph(
+        "g",
+        &graph,
+        |n| NodeStyle::default().with_label(n),
+        |w| Some(w.to_string()),
+    );
+
+    assert_eq!(
+        dot.to_string(),
+        "graph \"g\" {\n    \"0\" [shape=ellipse, label=\"a\"];\n    \"1\" [shape=ellipse, label=\"b\\\"\"];\n    \"0\" -- \"1\" [label=\"7\"];\n}\n"
+    );
+}
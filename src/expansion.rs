@@ -0,0 +1,86 @@
+use crate::Co2;
+
+/// Expands one axis: each empty line before a value adds `factor - 1` to it. Returns `None` on
+/// overflow.
+fn expand_axis(values: impl Iterator<Item = u64>, factor: u64) -> Option<Vec<u64>> {
+    let values = values.collect::<Vec<_>>();
+    let mut occupied = values.clone();
+    occupied.sort_unstable();
+    occupied.dedup();
+
+    values
+        .into_iter()
+        .map(|v| {
+            // The number of occupied lines before `v` gives the number of empty lines before it
+            let idx = occupied.partition_point(|&o| o < v) as u64;
+            let empty_before = v - idx;
+            (factor - 1).checked_mul(empty_before)?.checked_add(v)
+        })
+        .collect()
+}
+
+/// Expands the space between `points` so that every row and column containing no points becomes
+/// `factor` rows or columns wide
+///
+/// Empty lines are counted from zero, not from the first occupied line. Runs in O(n log n).
+/// Returns `None` if an expanded coordinate does not fit in a `u64`.
+pub fn expand(points: &[Co2<u64>], factor: u64) -> Option<Vec<Co2<u64>>> {
+    assert!(factor != 0, "expansion factor must be positive");
+
+    let rows = expand_axis(points.iter().map(|co| co[0]), factor)?;
+    let cols = expand_axis(points.iter().map(|co| co[1]), factor)?;
+
+    Some(
+        rows.into_iter()
+            .zip(cols)
+            .map(|(row, col)| Co2(row, col))
+            .collect(),
+    )
+}
+
+/// Returns the sum of |a - b| over all pairs of `values`
+fn pairwise_axis_sum(mut values: Vec<u64>) -> u128 {
+    values.sort_unstable();
+
+    // Each value is the larger of the pair with every value before it
+    let mut prefix = 0u128;
+    let mut sum = 0u128;
+    for (idx, &v) in values.iter().enumerate() {
+        sum += v as u128 * idx as u128 - prefix;
+        prefix += v as u128;
+    }
+    sum
+}
+
+/// Returns the sum of Manhattan distances between all pairs of `points` in O(n log n)
+pub fn pairwise_manhattan_sum(points: &[Co2<u64>]) -> u128 {
+    pairwise_axis_sum(points.iter().map(|co| co[0]).collect())
+        + pairwise_axis_sum(points.iter().map(|co| co[1]).collect())
+}
+
+#[test]
+fn expanded_distances() {
+    let galaxies = [
+        (0, 3),
+        (1, 7),
+        (2, 0),
+        (4, 6),
+        (5, 1),
+        (6, 9),
+        (8, 7),
+        (9, 0),
+        (9, 4),
+    ]
+    .map(Co2::from);
+
+    let sum = |factor| pairwise_manhattan_sum(&expand(&galaxies, factor).unwrap());
+    assert_eq!(expand(&galaxies, 2).unwrap()[..2], [Co2(0, 4), Co2(1, 9)]);
+    assert_eq!(sum(2), 374);
+    assert_eq!(sum(10), 1030);
+    assert_eq!(sum(100), 8410);
+
+    // Distances grow linearly with the factor, up to where coordinates no longer fit
+    let huge = 1_000_000_000_000_000;
+    assert_eq!(sum(huge), 82 * (huge as u128 - 1) + 292);
+    assert_eq!(expand(&galaxies.map(|co| co * 100_000), huge), None);
+}
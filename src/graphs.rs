@@ -0,0 +1,149 @@
+//! Conversions from grids and edge lists into `petgraph` graphs
+//!
+//! Once converted, the `petgraph::algo` module provides SCCs, toposort, dominators, connected
+//! components and shortest paths.
+use crate::{Co2, Dir, Grid, DIAGONAL_OFFSETS};
+use petgraph::{
+    graph::{DiGraph, NodeIndex},
+    graphmap::DiGraphMap,
+};
+use std::{collections::HashMap, hash::Hash};
+
+/// Neighbor rule stepping to the four orthogonally adjacent cells
+pub fn cardinal<T>(co: Co2<usize>, _: &T) -> impl Iterator<Item = Co2<usize>> {
+    Dir::ALL
+        .into_iter()
+        .map(move |dir| offset(co, dir.as_tuple()))
+}
+
+/// Neighbor rule stepping to the eight orthogonally and diagonally adjacent cells
+pub fn octile<T>(co: Co2<usize>, cell: &T) -> impl Iterator<Item = Co2<usize>> {
+    cardinal(co, cell).chain(
+        DIAGONAL_OFFSETS
+            .iter()
+            .map(move |&(dr, dc)| offset(co, (dr as isize, dc as isize))),
+    )
+}
+
+/// Steps `co` by `ofs`, wrapping out of bounds below zero instead of failing
+fn offset(co: Co2<usize>, ofs: (isize, isize)) -> Co2<usize> {
+    Co2(
+        co[0].wrapping_add_signed(ofs.0),
+        co[1].wrapping_add_signed(ofs.1),
+    )
+}
+
+/// Builds a graph map with a node for each passable cell and an edge from each passable cell to
+/// every passable cell its neighbor rule leads to
+///
+/// The neighbor rule receives the cell and its value, so that e.g. one-way tiles can restrict
+/// the directions. Candidates outside of the grid are ignored.
+pub fn grid_graphmap<T, I>(
+    grid: &Grid<T>,
+    passable: impl Fn(&T) -> bool,
+    neighbors: impl Fn(Co2<usize>, &T) -> I,
+) -> DiGraphMap<Co2<usize>, ()>
+where
+    I: IntoIterator<Item = Co2<usize>>,
+{
+    let mut graph = DiGraphMap::new();
+    for (co, cell) in grid.iter().filter(|(_, cell)| passable(cell)) {
+        graph.add_node(co);
+        for next in neighbors(co, cell) {
+            if grid.get(next).is_some_and(&passable) {
+                graph.add_edge(co, next, ());
+            }
+        }
+    }
+    graph
+}
+
+/// Builds a graph like `grid_graphmap`, but as an index-based `petgraph::Graph` with the map
+/// from each passable cell to its node
+pub fn grid_graph<T, I>(
+    grid: &Grid<T>,
+    passable: impl Fn(&T) -> bool,
+    neighbors: impl Fn(Co2<usize>, &T) -> I,
+) -> (DiGraph<Co2<usize>, ()>, HashMap<Co2<usize>, NodeIndex>)
+where
+    I: IntoIterator<Item = Co2<usize>>,
+{
+    from_edge_list(
+        grid.iter()
+            .filter(|(_, cell)| passable(cell))
+            .flat_map(|(co, cell)| {
+                neighbors(co, cell)
+                    .into_iter()
+                    .filter(|&next| grid.get(next).is_some_and(&passable))
+                    .map(move |next| (co, next, ()))
+            }),
+        grid.iter()
+            .filter(|(_, cell)| passable(cell))
+            .map(|(co, _)| co),
+    )
+}
+
+/// Builds a graph from `(from, to, weight)` edges between labeled nodes, such as names parsed
+/// from the input
+///
+/// Each label is interned into a single node, in order of first appearance. `nodes` adds labels
+/// that might have no edges; pass `[]` if there are none. Returns the graph with the map from
+/// each label to its node.
+pub fn from_edge_list<N, E>(
+    edges: impl IntoIterator<Item = (N, N, E)>,
+    nodes: impl IntoIterator<Item = N>,
+) -> (DiGraph<N, E>, HashMap<N, NodeIndex>)
+where
+    N: Hash + Eq + Clone,
+{
+    let mut graph = DiGraph::new();
+    let mut ids = HashMap::new();
+    let mut id = |graph: &mut DiGraph<N, E>, label: N| {
+        *ids.entry(label.clone())
+            .or_insert_with(|| graph.add_node(label))
+    };
+
+    for label in nodes {
+        id(&mut graph, label);
+    }
+    for (from, to, weight) in edges {
+        let (from, to) = (id(&mut graph, from), id(&mut graph, to));
+        graph.add_edge(from, to, weight);
+    }
+    (graph, ids)
+}
+
+#[test]
+fn grid_and_label_graphs() {
+    use petgraph::algo;
+
+    let grid = Grid::parse(
+        "..#.
+.##.
+#..>",
+    );
+    let open = |c: &char| *c != '#';
+    // `>` only leads east, which is out of bounds
+    let slopes = |co: Co2<usize>, c: &char| match c {
+        '>' => vec![Co2(co[0], co[1] + 1)],
+        _ => cardinal(co, c).collect(),
+    };
+
+    let map = grid_graphmap(&grid, open, slopes);
+    assert_eq!(map.node_count(), 8);
+    assert!(map.contains_edge(Co2(1, 3), Co2(2, 3)));
+    assert!(!map.contains_edge(Co2(2, 3), Co2(1, 3)));
+    assert_eq!(algo::connected_components(&map), 2);
+
+    let (graph, nodes) = grid_graph(&grid, open, octile);
+    assert_eq!(algo::connected_components(&graph), 1);
+    assert!(graph.contains_edge(nodes[&Co2(1, 0)], nodes[&Co2(2, 1)]));
+
+    let (graph, ids) = from_edge_list(
+        [("a", "b", 1), ("b", "c", 2), ("c", "a", 3), ("c", "d", 4)],
+        ["e"],
+    );
+    assert_eq!(graph.node_count(), 5);
+    assert_eq!(graph[ids["d"]], "d");
+    assert_eq!(algo::kosaraju_scc(&graph).len(), 3);
+}
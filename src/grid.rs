@@ -0,0 +1,210 @@
+use crate::{Co2, Dir};
+use anyhow::bail;
+use std::{fmt, ops};
+
+/// Dense 2D grid indexed by `Co2(row, col)`, stored row by row
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a `rows` x `cols` grid with every cell set to `fill`
+    pub fn new(rows: usize, cols: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            rows,
+            cols,
+            cells: vec![fill; rows * cols],
+        }
+    }
+
+    /// Creates a grid by calling `f` for each coordinate
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(Co2<usize>) -> T) -> Self {
+        let cells = (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| Co2(row, col)))
+            .map(&mut f)
+            .collect();
+        Grid { rows, cols, cells }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Number of cells in the grid
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, co: Co2<usize>) -> bool {
+        co[0] < self.rows && co[1] < self.cols
+    }
+
+    pub fn get(&self, co: Co2<usize>) -> Option<&T> {
+        self.contains(co).then(|| &self.cells[self.index_of(co)])
+    }
+
+    pub fn get_mut(&mut self, co: Co2<usize>) -> Option<&mut T> {
+        if self.contains(co) {
+            let idx = self.index_of(co);
+            Some(&mut self.cells[idx])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the index of `co` in the row-major cell storage
+    pub fn index_of(&self, co: Co2<usize>) -> usize {
+        co[0] * self.cols + co[1]
+    }
+
+    /// Returns the coordinate of the cell at `idx` in the row-major cell storage
+    pub fn co_of(&self, idx: usize) -> Co2<usize> {
+        Co2(idx / self.cols, idx % self.cols)
+    }
+
+    /// Returns the neighbor of `co` in direction `dir`, if it is within the grid
+    pub fn step(&self, co: Co2<usize>, dir: Dir) -> Option<Co2<usize>> {
+        let (dr, dc) = dir.as_tuple();
+        let row = co[0].checked_add_signed(dr)?;
+        let col = co[1].checked_add_signed(dc)?;
+        let nco = Co2(row, col);
+        self.contains(nco).then_some(nco)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.cols.max(1))
+    }
+
+    /// Returns an iterator across all cells in row-major order along with their coordinates
+    pub fn iter(&self) -> impl Iterator<Item = (Co2<usize>, &T)> {
+        let cols = self.cols;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(idx, t)| (Co2(idx / cols, idx % cols), t))
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// Returns the coordinate of the first cell equal to `target`
+    pub fn find(&self, target: &T) -> Option<Co2<usize>>
+    where
+        T: PartialEq,
+    {
+        self.cells
+            .iter()
+            .position(|t| t == target)
+            .map(|idx| self.co_of(idx))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            rows: self.rows,
+            cols: self.cols,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl Grid<char> {
+    /// Parses a grid from lines of characters
+    ///
+    /// Panics if the lines are not all the same length.
+    pub fn parse(input: &str) -> Self {
+        Self::try_parse(input).unwrap()
+    }
+
+    /// Parses a grid from lines of characters, failing if they are not all the same length
+    pub fn try_parse(input: &str) -> anyhow::Result<Self> {
+        Self::try_from_rows(input.lines().map(|line| line.chars().collect()).collect())
+    }
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its rows, failing if they are not all the same length
+    pub fn try_from_rows(rows: Vec<Vec<T>>) -> anyhow::Result<Self> {
+        let cols = rows.first().map(Vec::len).unwrap_or(0);
+        if let Some((row, line)) = rows.iter().enumerate().find(|(_, l)| l.len() != cols) {
+            bail!(
+                "ragged grid: row {row} has {} cells, expected {cols}",
+                line.len()
+            );
+        }
+
+        Ok(Grid {
+            rows: rows.len(),
+            cols,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+}
+
+impl<T> From<Vec<Vec<T>>> for Grid<T> {
+    /// Panics if the rows are not all the same length
+    fn from(value: Vec<Vec<T>>) -> Self {
+        Self::try_from_rows(value).unwrap()
+    }
+}
+
+impl<T> ops::Index<Co2<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, co: Co2<usize>) -> &Self::Output {
+        assert!(self.contains(co), "{co:?} out of bounds");
+        &self.cells[self.index_of(co)]
+    }
+}
+
+impl<T> ops::IndexMut<Co2<usize>> for Grid<T> {
+    fn index_mut(&mut self, co: Co2<usize>) -> &mut Self::Output {
+        assert!(self.contains(co), "{co:?} out of bounds");
+        let idx = self.index_of(co);
+        &mut self.cells[idx]
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.iter_rows() {
+            for t in row {
+                write!(f, "{t}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn grid_parse() {
+    let grid = Grid::parse("ab\ncd\n");
+
+    assert_eq!((grid.rows(), grid.cols()), (2, 2));
+    assert_eq!(grid[Co2(1, 0)], 'c');
+    assert_eq!(grid.step(Co2(0, 0), Dir::West), None);
+    assert_eq!(grid.step(Co2(0, 0), Dir::South), Some(Co2(1, 0)));
+    assert_eq!(grid.to_string(), "ab\ncd\n");
+
+    assert_eq!(Grid::try_parse("ab\r\ncd\r\n").unwrap(), grid);
+    assert!(Grid::try_parse("ab\ncd\ne").is_err());
+}
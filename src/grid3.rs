@@ -0,0 +1,232 @@
+use crate::{Co3, Grid};
+use std::{fmt::Write, ops};
+
+/// Dense 3D grid indexed by `Co3(x, y, z)`, stored z-layer by z-layer with x changing fastest
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid3<T> {
+    size: Co3<usize>,
+    cells: Vec<T>,
+}
+
+/// What is seen along a line of sight through a grid of optional items
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sight<T> {
+    Empty,
+    /// Only this item, possibly in several cells
+    One(T),
+    /// More than one distinct item
+    Many,
+}
+
+impl<T> Grid3<T> {
+    /// Creates a grid spanning `size` (x, y, z) with every cell set to `fill`
+    pub fn new(size: Co3<usize>, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid3 {
+            size,
+            cells: vec![fill; size[0] * size[1] * size[2]],
+        }
+    }
+
+    /// Creates a grid by calling `f` for each coordinate
+    pub fn from_fn(size: Co3<usize>, mut f: impl FnMut(Co3<usize>) -> T) -> Self {
+        let (xs, ys, zs) = size.as_tuple();
+        let cells = (0..zs)
+            .flat_map(|z| (0..ys).flat_map(move |y| (0..xs).map(move |x| Co3(x, y, z))))
+            .map(&mut f)
+            .collect();
+        Grid3 { size, cells }
+    }
+
+    /// Number of cells along x, y and z
+    pub fn size(&self) -> Co3<usize> {
+        self.size
+    }
+
+    /// Number of cells in the grid
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, co: Co3<usize>) -> bool {
+        (0..3).all(|axis| co[axis] < self.size[axis])
+    }
+
+    pub fn get(&self, co: Co3<usize>) -> Option<&T> {
+        self.contains(co).then(|| &self.cells[self.index_of(co)])
+    }
+
+    pub fn get_mut(&mut self, co: Co3<usize>) -> Option<&mut T> {
+        if self.contains(co) {
+            let idx = self.index_of(co);
+            Some(&mut self.cells[idx])
+        } else {
+            None
+        }
+    }
+
+    /// Replaces the cell at `co`, returning its previous value, or `None` if `co` is outside of
+    /// the grid
+    pub fn set(&mut self, co: Co3<usize>, value: T) -> Option<T> {
+        self.get_mut(co).map(|cell| std::mem::replace(cell, value))
+    }
+
+    /// Returns the index of `co` in the cell storage
+    pub fn index_of(&self, co: Co3<usize>) -> usize {
+        (co[2] * self.size[1] + co[1]) * self.size[0] + co[0]
+    }
+
+    /// Returns the cells at height `z`, y-row by y-row
+    pub fn layer(&self, z: usize) -> &[T] {
+        let area = self.size[0] * self.size[1];
+        &self.cells[z * area..(z + 1) * area]
+    }
+
+    pub fn layer_mut(&mut self, z: usize) -> &mut [T] {
+        let area = self.size[0] * self.size[1];
+        &mut self.cells[z * area..(z + 1) * area]
+    }
+
+    /// Returns an iterator across the cells above `(x, y)`, from the bottom up
+    pub fn column(&self, x: usize, y: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.size[0] && y < self.size[1], "column out of bounds");
+        let area = self.size[0] * self.size[1];
+        self.cells
+            .iter()
+            .skip(y * self.size[0] + x)
+            .step_by(area.max(1))
+    }
+
+    /// Returns an iterator across all cells in storage order along with their coordinates
+    pub fn iter(&self) -> impl Iterator<Item = (Co3<usize>, &T)> {
+        let (xs, ys, _) = self.size.as_tuple();
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(idx, t)| (Co3(idx % xs, idx / xs % ys, idx / (xs * ys)), t))
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+}
+
+impl<T: PartialEq> Grid3<Option<T>> {
+    /// Returns the view from the front, looking along y, as a grid indexed by `Co2(z, x)`
+    pub fn project_xz(&self) -> Grid<Sight<&T>> {
+        self.project(0, 1)
+    }
+
+    /// Returns the view from the side, looking along x, as a grid indexed by `Co2(z, y)`
+    pub fn project_yz(&self) -> Grid<Sight<&T>> {
+        self.project(1, 0)
+    }
+
+    fn project(&self, across: usize, along: usize) -> Grid<Sight<&T>> {
+        Grid::from_fn(self.size[2], self.size[across], |view_co| {
+            (0..self.size[along]).fold(Sight::Empty, |sight, b| {
+                let mut co = Co3(0, 0, view_co[0]);
+                co[across] = view_co[1];
+                co[along] = b;
+                match (sight, &self[co]) {
+                    (sight, None) => sight,
+                    (Sight::Empty, Some(t)) => Sight::One(t),
+                    (Sight::One(seen), Some(t)) if seen == t => Sight::One(seen),
+                    _ => Sight::Many,
+                }
+            })
+        })
+    }
+
+    /// Draws the front view like the day 22 puzzle does, with `label` naming each item
+    pub fn side_view_xz(&self, label: impl Fn(&T) -> char) -> String {
+        side_view(&self.project_xz(), 'x', label)
+    }
+
+    /// Draws the side view like the day 22 puzzle does, with `label` naming each item
+    pub fn side_view_yz(&self, label: impl Fn(&T) -> char) -> String {
+        side_view(&self.project_yz(), 'y', label)
+    }
+}
+
+/// Draws a projection with z growing upwards, axis labels and the ground at z = 0
+fn side_view<T>(view: &Grid<Sight<&T>>, axis: char, label: impl Fn(&T) -> char) -> String {
+    let width = view.cols();
+    let mut out = format!("{:>1$}\n", axis, width / 2 + 1);
+    out.extend((0..width).map(|a| char::from_digit((a % 10) as u32, 10).unwrap()));
+    out.push('\n');
+    for z in (0..view.rows()).rev() {
+        if z == 0 {
+            out.push_str(&"-".repeat(width));
+        } else {
+            out.extend(view.row(z).iter().map(|sight| match sight {
+                Sight::Empty => '.',
+                Sight::One(t) => label(t),
+                Sight::Many => '?',
+            }));
+        }
+        write!(out, " {z}").unwrap();
+        if z == view.rows() / 2 {
+            out.push_str(" z");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+impl<T> ops::Index<Co3<usize>> for Grid3<T> {
+    type Output = T;
+
+    fn index(&self, co: Co3<usize>) -> &Self::Output {
+        assert!(self.contains(co), "{co:?} out of bounds");
+        &self.cells[self.index_of(co)]
+    }
+}
+
+impl<T> ops::IndexMut<Co3<usize>> for Grid3<T> {
+    fn index_mut(&mut self, co: Co3<usize>) -> &mut Self::Output {
+        assert!(self.contains(co), "{co:?} out of bounds");
+        let idx = self.index_of(co);
+        &mut self.cells[idx]
+    }
+}
+
+#[test]
+fn grid3_side_views() {
+    use crate::Cuboid;
+
+    // The bricks of the day 22 example, A to G
+    let bricks = [
+        (Co3(1, 0, 1), Co3(1, 2, 1)),
+        (Co3(0, 0, 2), Co3(2, 0, 2)),
+        (Co3(0, 2, 3), Co3(2, 2, 3)),
+        (Co3(0, 0, 4), Co3(0, 2, 4)),
+        (Co3(2, 0, 5), Co3(2, 2, 5)),
+        (Co3(0, 1, 6), Co3(2, 1, 6)),
+        (Co3(1, 1, 8), Co3(1, 1, 9)),
+    ];
+    let mut grid = Grid3::new(Co3(3, 3, 10), None);
+    for (idx, (a, b)) in bricks.into_iter().enumerate() {
+        for co in Cuboid::new(a, b).points() {
+            grid.set(co, Some(idx));
+        }
+    }
+    assert_eq!(grid.layer(2)[..4], [Some(1), Some(1), Some(1), None]);
+    assert_eq!(grid.column(1, 1).flatten().count(), 4);
+
+    let label = |&idx: &usize| (b'A' + idx as u8) as char;
+    assert_eq!(
+        grid.side_view_xz(label),
+        " x\n012\n.G. 9\n.G. 8\n... 7\nFFF 6\n..E 5 z\nD.. 4\nCCC 3\nBBB 2\n.A. 1\n--- 0\n"
+    );
+    assert_eq!(
+        grid.side_view_yz(label),
+        " y\n012\n.G. 9\n.G. 8\n... 7\n.F. 6\nEEE 5 z\nDDD 4\n..C 3\nB.. 2\nAAA 1\n--- 0\n"
+    );
+}
@@ -0,0 +1,162 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{BuildHasher, BuildHasherDefault, Hash, Hasher},
+};
+
+/// Hasher computing the Holiday ASCII String Helper algorithm (HASH) of the bytes written to it
+///
+/// Each byte is added to the current value, which is then multiplied by 17, modulo 256. The
+/// 8-bit value is spread over the whole `u64` by `finish`, since hash tables take part of their
+/// probing from the top bits, while the low byte stays the HASH itself.
+///
+/// Strings are hashed with a terminator after their text, so key maps by `HolidayKey` to hash
+/// the text alone.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HolidayHasher(u8);
+
+/// Large odd constant spreading the 8-bit HASH over 64 bits without merging any two values. Its
+/// low byte is one, so that the low byte of the product is the HASH.
+const HOLIDAY_SPREAD: u64 = 0x9e37_79b9_7f4a_7c01;
+
+impl Hasher for HolidayHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = self.0.wrapping_add(b).wrapping_mul(17);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        (self.0 as u64).wrapping_mul(HOLIDAY_SPREAD)
+    }
+}
+
+/// `BuildHasher` for `HolidayHasher`, for maps keyed by `HolidayKey`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HolidayHash;
+
+impl BuildHasher for HolidayHash {
+    type Hasher = HolidayHasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        HolidayHasher::default()
+    }
+}
+
+/// String key whose `Hash` writes only its text, so that `HolidayHash` hashes it to its HASH
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct HolidayKey<S>(pub S);
+
+impl<S: AsRef<str>> Hash for HolidayKey<S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write(self.0.as_ref().as_bytes());
+    }
+}
+
+/// Returns the HASH of `s`
+pub fn holiday_hash(s: &str) -> u8 {
+    HolidayHash.hash_one(HolidayKey(s)) as u8
+}
+
+/// Fast non-cryptographic hasher in the style of rustc's FxHash
+///
+/// Each word is folded in with a rotate, xor and multiply. Good enough for the small integer and
+/// coordinate keys of puzzles, but trivially attackable, so never use it on untrusted keys.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FxHasher(u64);
+
+const FX_SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+impl FxHasher {
+    fn add(&mut self, word: u64) {
+        self.0 = (self.0.rotate_left(5) ^ word).wrapping_mul(FX_SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.add(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+        let rest = chunks.remainder();
+        if !rest.is_empty() {
+            let mut word = [0; 8];
+            word[..rest.len()].copy_from_slice(rest);
+            self.add(u64::from_le_bytes(word));
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.add(i as u64);
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.add(i as u64);
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.add(i as u64);
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.add(i);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.add(i as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// `BuildHasher` for `FxHasher`
+pub type FxBuildHasher = BuildHasherDefault<FxHasher>;
+
+/// `HashMap` using `FxHasher`. Create with `FastHashMap::default()`.
+pub type FastHashMap<K, V> = HashMap<K, V, FxBuildHasher>;
+
+/// `HashSet` using `FxHasher`. Create with `FastHashSet::default()`.
+pub type FastHashSet<T> = HashSet<T, FxBuildHasher>;
+
+#[test]
+fn holiday_hash_example() {
+    assert_eq!(holiday_hash("HASH"), 52);
+    assert_eq!(holiday_hash("rn"), 0);
+    assert_eq!(holiday_hash("qp"), 1);
+
+    // The HASH spread over 64 bits, so distinct values stay distinct and reach the top bits
+    for label in ["HASH", "rn", "qp", "cm", "ot"] {
+        let hash = HolidayHash.hash_one(HolidayKey(label));
+        assert_eq!(
+            hash,
+            (holiday_hash(label) as u64).wrapping_mul(HOLIDAY_SPREAD)
+        );
+        assert_eq!(hash, HolidayHash.hash_one(HolidayKey(label.to_string())));
+    }
+    assert_ne!(HolidayHash.hash_one(HolidayKey("qp")) >> 57, 0);
+    assert_ne!(HolidayHash.hash_one(300u32), HolidayHash.hash_one(44u32));
+}
+
+#[test]
+fn fast_hash_collections() {
+    use crate::Co2;
+
+    let mut visited = FastHashSet::default();
+    assert!(visited.insert(Co2(1usize, 2usize)));
+    assert!(!visited.insert(Co2(1, 2)));
+    assert!(visited.insert(Co2(2, 1)));
+
+    let mut counts: FastHashMap<&str, usize> = FastHashMap::default();
+    for word in "a b a c a".split(' ') {
+        *counts.entry(word).or_default() += 1;
+    }
+    assert_eq!(counts["a"], 3);
+
+    // Unseeded, so hashes are the same in every process
+    assert_eq!(
+        FxBuildHasher::default().hash_one(42u64),
+        42u64.wrapping_mul(FX_SEED)
+    );
+}
@@ -0,0 +1,166 @@
+use std::{collections::HashMap, ops};
+
+/// Dense integer ID of a name held by an `Interner`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Id(u32);
+
+impl Id {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Assigns IDs to names in order of first appearance, so that parsed identifiers can be compared
+/// and looked up without hashing strings
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    ids: HashMap<String, Id>,
+    names: Vec<String>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the ID of `name`, assigning the next free one if it has not been seen before
+    pub fn intern(&mut self, name: &str) -> Id {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = Id(u32::try_from(self.names.len()).expect("too many names to intern"));
+        self.ids.insert(name.to_string(), id);
+        self.names.push(name.to_string());
+        id
+    }
+
+    /// Returns the ID of `name` if it has been interned
+    pub fn get(&self, name: &str) -> Option<Id> {
+        self.ids.get(name).copied()
+    }
+
+    /// Returns the name behind `id`
+    pub fn resolve(&self, id: Id) -> &str {
+        &self.names[id.index()]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Returns an iterator across the IDs and their names in order of assignment
+    pub fn iter(&self) -> impl Iterator<Item = (Id, &str)> {
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| (Id(idx as u32), name.as_str()))
+    }
+}
+
+/// Storage with one slot per `Id`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdVec<T>(Vec<T>);
+
+impl<T> Default for IdVec<T> {
+    fn default() -> Self {
+        IdVec(vec![])
+    }
+}
+
+impl<T> IdVec<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates storage for the first `len` IDs, e.g. every ID of an `Interner`
+    pub fn filled(len: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        IdVec(vec![value; len])
+    }
+
+    pub fn from_fn(len: usize, mut f: impl FnMut(Id) -> T) -> Self {
+        IdVec((0..len).map(|idx| f(Id(idx as u32))).collect())
+    }
+
+    /// Stores `value` for `id`, filling any missing slots before it with defaults
+    pub fn insert(&mut self, id: Id, value: T)
+    where
+        T: Default,
+    {
+        if id.index() >= self.0.len() {
+            self.0.resize_with(id.index() + 1, T::default);
+        }
+        self.0[id.index()] = value;
+    }
+
+    pub fn get(&self, id: Id) -> Option<&T> {
+        self.0.get(id.index())
+    }
+
+    pub fn get_mut(&mut self, id: Id) -> Option<&mut T> {
+        self.0.get_mut(id.index())
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Id, &T)> {
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(idx, value)| (Id(idx as u32), value))
+    }
+
+    pub fn values(&self) -> &[T] {
+        &self.0
+    }
+}
+
+/// Collects values for consecutive IDs starting from the first
+impl<T> FromIterator<T> for IdVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        IdVec(iter.into_iter().collect())
+    }
+}
+
+impl<T> ops::Index<Id> for IdVec<T> {
+    type Output = T;
+
+    fn index(&self, id: Id) -> &Self::Output {
+        &self.0[id.index()]
+    }
+}
+
+impl<T> ops::IndexMut<Id> for IdVec<T> {
+    fn index_mut(&mut self, id: Id) -> &mut Self::Output {
+        &mut self.0[id.index()]
+    }
+}
+
+#[test]
+fn intern_and_index() {
+    let mut names = Interner::new();
+    let aaa = names.intern("AAA");
+    let zzz = names.intern("ZZZ");
+    assert_eq!(names.intern("AAA"), aaa);
+    assert_eq!((aaa.index(), zzz.index()), (0, 1));
+    assert_eq!(names.resolve(zzz), "ZZZ");
+    assert_eq!(names.get("BBB"), None);
+
+    let mut lefts = IdVec::new();
+    lefts.insert(zzz, Some(aaa));
+    assert_eq!(lefts[aaa], None);
+    assert_eq!(lefts[zzz], Some(aaa));
+    assert_eq!(lefts.iter().count(), names.len());
+}
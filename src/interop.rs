@@ -0,0 +1,147 @@
+//! Conversions to and from the `aochelpers` types
+//!
+//! `Co2` is (row, col) while `aochelpers::Coordinate` is (x, y) with y growing southwards, as
+//! in `parse_number_grid` and `Coordinate::neighbour`. The conversions map row to y and col to
+//! x, so that a cell keeps its place on the screen: `Co2(row, col)` becomes
+//! `Coordinate { x: col, y: row }`. `Co3` has no screen convention and maps field by field to
+//! `Coordinate3d { x, y, z }`.
+use crate::{Co2, Co3, Dir, Grid};
+use anyhow::{bail, Context};
+use aochelpers::{Coordinate, Coordinate3d, Direction};
+use std::{collections::HashMap, num::TryFromIntError};
+
+impl<T> From<Coordinate<T>> for Co2<T> {
+    fn from(value: Coordinate<T>) -> Self {
+        Co2(value.y, value.x)
+    }
+}
+
+impl<T> From<Co2<T>> for Coordinate<T> {
+    fn from(value: Co2<T>) -> Self {
+        let [y, x] = value.into_array();
+        Coordinate { x, y }
+    }
+}
+
+macro_rules! impl_signed_coordinate {
+    ($($signed:ty),*) => {$(
+        /// Fails on negative coordinates, which have no cell
+        impl TryFrom<Coordinate<$signed>> for Co2<usize> {
+            type Error = TryFromIntError;
+
+            fn try_from(value: Coordinate<$signed>) -> Result<Self, Self::Error> {
+                Ok(Co2(usize::try_from(value.y)?, usize::try_from(value.x)?))
+            }
+        }
+
+        impl TryFrom<Co2<usize>> for Coordinate<$signed> {
+            type Error = TryFromIntError;
+
+            fn try_from(value: Co2<usize>) -> Result<Self, Self::Error> {
+                Ok(Coordinate {
+                    x: <$signed>::try_from(value[1])?,
+                    y: <$signed>::try_from(value[0])?,
+                })
+            }
+        }
+    )*};
+}
+
+impl_signed_coordinate!(i32, i64, isize);
+
+impl<T> From<Coordinate3d<T>> for Co3<T> {
+    fn from(value: Coordinate3d<T>) -> Self {
+        Co3(value.x, value.y, value.z)
+    }
+}
+
+impl<T> From<Co3<T>> for Coordinate3d<T> {
+    fn from(value: Co3<T>) -> Self {
+        let [x, y, z] = value.into_array();
+        Coordinate3d { x, y, z }
+    }
+}
+
+impl From<Dir> for Direction {
+    fn from(value: Dir) -> Self {
+        match value {
+            Dir::North => Direction::North,
+            Dir::East => Direction::East,
+            Dir::South => Direction::South,
+            Dir::West => Direction::West,
+        }
+    }
+}
+
+/// Fails on the diagonal directions
+impl TryFrom<Direction> for Dir {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Direction) -> Result<Self, Self::Error> {
+        Ok(match value {
+            Direction::North => Dir::North,
+            Direction::East => Dir::East,
+            Direction::South => Dir::South,
+            Direction::West => Dir::West,
+            diagonal => bail!("{diagonal:?} is not a cardinal direction"),
+        })
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates the smallest grid covering every cell of a sparse `aochelpers` grid such as the
+    /// one returned by `parse_number_grid`, filling cells missing from it with `fill`
+    ///
+    /// Fails if a coordinate is negative.
+    pub fn from_coordinates<I>(cells: &HashMap<Coordinate<I>, T>, fill: T) -> anyhow::Result<Self>
+    where
+        I: Copy + TryInto<usize>,
+    {
+        let cells = cells
+            .iter()
+            .map(|(co, value)| {
+                let row = co.y.try_into().ok().context("negative y coordinate")?;
+                let col = co.x.try_into().ok().context("negative x coordinate")?;
+                Ok((Co2(row, col), value))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let rows = cells.iter().map(|(co, _)| co[0] + 1).max().unwrap_or(0);
+        let cols = cells.iter().map(|(co, _)| co[1] + 1).max().unwrap_or(0);
+        let mut grid = Grid::new(rows, cols, fill);
+        for (co, value) in cells {
+            grid[co] = value.clone();
+        }
+        Ok(grid)
+    }
+}
+
+#[test]
+fn aochelpers_axes() {
+    use aochelpers::parse_number_grid;
+
+    let coordinate = Coordinate { x: 2, y: 1 };
+    assert_eq!(Co2::from(coordinate), Co2(1, 2));
+    let back: Coordinate<i32> = Co2(1, 2).into();
+    assert_eq!(back, coordinate);
+    assert!(Co2::<usize>::try_from(Coordinate { x: -1i32, y: 0 }).is_err());
+
+    // Stepping agrees in both conventions
+    for dir in Dir::ALL {
+        let next = Coordinate::<i32>::try_from(Co2(5usize, 5usize))
+            .unwrap()
+            .neighbour(dir.into());
+        let (dr, dc) = dir.as_tuple();
+        assert_eq!(
+            Co2::<usize>::try_from(next).ok(),
+            Co2::try_from((5 + dr, 5 + dc)).ok()
+        );
+        assert_eq!(Dir::try_from(Direction::from(dir)).unwrap(), dir);
+    }
+    assert!(Dir::try_from(Direction::NorthEast).is_err());
+
+    let numbers: HashMap<Coordinate<i32>, u8> = parse_number_grid("123\n456");
+    let grid = Grid::from_coordinates(&numbers, 0).unwrap();
+    assert_eq!((grid.rows(), grid.cols()), (2, 3));
+    assert_eq!(grid[Co2(1, 0)], 4);
+}
@@ -1,8 +1,52 @@
-mod co2;
-mod co3;
+pub mod beam;
+mod bitset;
+pub mod cards;
+mod co;
+mod crt;
+pub mod crucible;
+mod cycle;
+mod dir;
+mod distance;
+pub mod dot;
+mod expansion;
+pub mod graphs;
+mod grid;
+mod grid3;
+mod hash;
+mod interner;
+mod interop;
+mod memo;
+pub mod mincut;
+mod neighbors;
+mod ordered_map;
+pub mod pipes;
+mod rect;
+mod reflection;
+pub mod regions;
+mod rocks;
+pub mod sequence;
+mod tokens;
+mod union_find;
 
-pub use co2::*;
-pub use co3::*;
+pub use bitset::*;
+pub use co::*;
+pub use crt::*;
+pub use cycle::*;
+pub use dir::*;
+pub use distance::*;
+pub use expansion::*;
+pub use grid::*;
+pub use grid3::*;
+pub use hash::*;
+pub use interner::*;
+pub use memo::*;
+pub use neighbors::*;
+pub use ordered_map::*;
+pub use rect::*;
+pub use reflection::*;
+pub use rocks::*;
+pub use tokens::*;
+pub use union_find::*;
 
 pub const CARDINAL_OFFSETS: &[(i64, i64)] = &[(-1, 0), (1, 0), (0, -1), (0, 1)];
 pub const DIAGONAL_OFFSETS: &[(i64, i64)] = &[(-1, -1), (1, -1), (1, 1), (-1, 1)];
@@ -0,0 +1,102 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Table of computed values for recursive functions, counting how often it is used
+///
+/// The computing closure receives the table itself, so that a recursive function can pass it on
+/// to its recursive calls.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    values: HashMap<K, V>,
+    stats: MemoStats,
+}
+
+/// Lookups into a `Memo` since it was created or last cleared
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoStats {
+    /// Lookups answered from the table
+    pub hits: u64,
+    /// Lookups that had to compute the value
+    pub misses: u64,
+}
+
+impl MemoStats {
+    /// Share of lookups answered from the table, zero if there were none
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo {
+            values: HashMap::new(),
+            stats: MemoStats::default(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the value for `key`, computing it with `f` on the first lookup
+    pub fn get_or_compute(&mut self, key: K, f: impl FnOnce(&mut Self, &K) -> V) -> V {
+        if let Some(value) = self.values.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+        self.stats.misses += 1;
+        let value = f(self, &key);
+        self.values.insert(key, value.clone());
+        value
+    }
+
+    /// Returns the value for `key` if it has been computed, without counting the lookup
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.values.get(key)
+    }
+
+    /// Number of computed values
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Forgets every computed value and resets the statistics
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.stats = MemoStats::default();
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        self.stats
+    }
+}
+
+#[test]
+fn memo_recursion() {
+    // Paths through a grid moving only right or down
+    fn paths(co: (u32, u32), memo: &mut Memo<(u32, u32), u64>) -> u64 {
+        memo.get_or_compute(co, |memo, &(row, col)| match (row, col) {
+            (0, _) | (_, 0) => 1,
+            _ => paths((row - 1, col), memo) + paths((row, col - 1), memo),
+        })
+    }
+
+    let mut memo = Memo::new();
+    assert_eq!(paths((16, 16), &mut memo), 601080390);
+    assert_eq!(memo.len(), 17 * 17 - 1);
+    assert_eq!(memo.stats().misses, memo.len() as u64);
+    assert!(memo.stats().hit_rate() > 0.4);
+
+    memo.clear();
+    assert!(memo.is_empty());
+    assert_eq!(memo.stats(), MemoStats::default());
+}
@@ -0,0 +1,343 @@
+//! Global minimum cuts of undirected graphs
+//!
+//! `stoer_wagner` is exact and deterministic. `karger` contracts random edges and is usually
+//! faster on sparse graphs with a small cut, at the cost of only finding the minimum with high
+//! probability.
+use crate::UnionFind;
+use anyhow::{bail, Context};
+use petgraph::visit::{EdgeRef, IntoEdgeReferences, IntoNodeIdentifiers, NodeCount, NodeIndexable};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+/// Undirected multigraph with optionally named nodes
+#[derive(Debug, Clone, Default)]
+pub struct CutGraph {
+    names: Vec<String>,
+    edges: Vec<(usize, usize)>,
+}
+
+/// A cut splitting the nodes of a graph in two
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinCut {
+    /// Edges crossing the cut as node index pairs, in the order they were added to the graph
+    pub edges: Vec<(usize, usize)>,
+    /// Whether each node is on the first side of the cut
+    pub side: Vec<bool>,
+}
+
+impl MinCut {
+    /// Returns the number of edges crossing the cut
+    pub fn weight(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Returns the number of nodes on either side of the cut
+    pub fn sizes(&self) -> (usize, usize) {
+        let first = self.side.iter().filter(|&&s| s).count();
+        (first, self.side.len() - first)
+    }
+}
+
+impl CutGraph {
+    /// Creates a graph of `nodes` unnamed nodes and no edges
+    pub fn new(nodes: usize) -> Self {
+        CutGraph {
+            names: (0..nodes).map(|idx| idx.to_string()).collect(),
+            edges: vec![],
+        }
+    }
+
+    /// Parses lines of the form `name: a b c`, each connecting `name` to every listed node
+    pub fn parse(input: &str) -> anyhow::Result<Self> {
+        let mut ids = HashMap::new();
+        let mut graph = CutGraph::default();
+        let mut id = |graph: &mut CutGraph, name: &str| {
+            *ids.entry(name.to_string()).or_insert_with(|| {
+                graph.names.push(name.to_string());
+                graph.names.len() - 1
+            })
+        };
+
+        for line in input.lines().filter(|line| !line.trim().is_empty()) {
+            let (name, others) = line
+                .split_once(':')
+                .with_context(|| format!("missing ':' in {line:?}"))?;
+            let name = name.trim();
+            if name.is_empty() {
+                bail!("missing node name in {line:?}");
+            }
+            let a = id(&mut graph, name);
+            for other in others.split_whitespace() {
+                let b = id(&mut graph, other);
+                graph.add_edge(a, b);
+            }
+        }
+        Ok(graph)
+    }
+
+    /// Copies the nodes and edges of a `petgraph` graph, ignoring edge direction and weights
+    ///
+    /// Nodes are renumbered densely in iteration order, skipping the holes left by removed nodes
+    /// in a `StableGraph`. Each node is named after its index in `graph`.
+    pub fn from_petgraph<G>(graph: G) -> Self
+    where
+        G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + NodeCount,
+    {
+        let mut dense = vec![usize::MAX; graph.node_bound()];
+        let mut cut_graph = CutGraph {
+            names: Vec::with_capacity(graph.node_count()),
+            edges: vec![],
+        };
+        for node in graph.node_identifiers() {
+            let idx = graph.to_index(node);
+            dense[idx] = cut_graph.names.len();
+            cut_graph.names.push(idx.to_string());
+        }
+        for edge in graph.edge_references() {
+            let a = dense[graph.to_index(edge.source())];
+            let b = dense[graph.to_index(edge.target())];
+            cut_graph.add_edge(a, b);
+        }
+        cut_graph
+    }
+
+    /// Adds an edge between `a` and `b`. Self-loops never cross a cut and are dropped.
+    pub fn add_edge(&mut self, a: usize, b: usize) {
+        assert!(a.max(b) < self.names.len(), "node index out of bounds");
+        if a != b {
+            self.edges.push((a, b));
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn edges(&self) -> &[(usize, usize)] {
+        &self.edges
+    }
+
+    /// Returns the name of the node at `idx`, or its index if it was not parsed from a name
+    pub fn name(&self, idx: usize) -> &str {
+        &self.names[idx]
+    }
+
+    /// Finds a minimum cut with the Stoer-Wagner algorithm in O(V E log V)
+    ///
+    /// Returns `None` if the graph has fewer than two nodes.
+    pub fn stoer_wagner(&self) -> Option<MinCut> {
+        let n = self.node_count();
+        if n < 2 {
+            return None;
+        }
+
+        let mut adj = vec![HashMap::<usize, usize>::new(); n];
+        for &(a, b) in &self.edges {
+            *adj[a].entry(b).or_default() += 1;
+            *adj[b].entry(a).or_default() += 1;
+        }
+        let mut members = (0..n).map(|v| vec![v]).collect::<Vec<_>>();
+        let mut active = (0..n).collect::<Vec<_>>();
+        let mut best: Option<(usize, Vec<usize>)> = None;
+
+        while active.len() > 1 {
+            // Maximum adjacency order: repeatedly add the node most tightly connected to the set
+            let mut weight = vec![0; n];
+            let mut added = vec![false; n];
+            let mut heap = active
+                .iter()
+                .map(|&v| (0, Reverse(v)))
+                .collect::<BinaryHeap<_>>();
+            let (mut s, mut t) = (usize::MAX, usize::MAX);
+            while let Some((w, Reverse(v))) = heap.pop() {
+                if added[v] || w != weight[v] {
+                    continue;
+                }
+                added[v] = true;
+                (s, t) = (t, v);
+                for (&u, &uw) in &adj[v] {
+                    if !added[u] {
+                        weight[u] += uw;
+                        heap.push((weight[u], Reverse(u)));
+                    }
+                }
+            }
+
+            // The cut of the phase separates the last node added from the rest
+            if best.as_ref().is_none_or(|(w, _)| weight[t] < *w) {
+                best = Some((weight[t], members[t].clone()));
+            }
+
+            // Merge t into s
+            for (u, uw) in std::mem::take(&mut adj[t]) {
+                adj[u].remove(&t);
+                if u != s {
+                    *adj[s].entry(u).or_default() += uw;
+                    *adj[u].entry(s).or_default() += uw;
+                }
+            }
+            let moved = std::mem::take(&mut members[t]);
+            members[s].extend(moved);
+            active.retain(|&v| v != t);
+        }
+
+        let (_, nodes) = best?;
+        let mut side = vec![false; n];
+        for v in nodes {
+            side[v] = true;
+        }
+        Some(self.cut(side))
+    }
+
+    /// Runs `trials` rounds of Karger's contraction from a deterministic `seed` and returns the
+    /// smallest cut found
+    ///
+    /// A single round finds a given minimum cut with probability at least 2 / V^2; sparse graphs
+    /// with a small cut fare much better in practice. Returns `None` if the graph has fewer than
+    /// two nodes.
+    pub fn karger(&self, seed: u64, trials: usize) -> Option<MinCut> {
+        let n = self.node_count();
+        if n < 2 {
+            return None;
+        }
+
+        let mut rng = XorShift(seed | 1);
+        let mut best: Option<MinCut> = None;
+        for _ in 0..trials {
+            let cut = self.contract(&mut rng);
+            if best.as_ref().is_none_or(|b| cut.weight() < b.weight()) {
+                best = Some(cut);
+            }
+        }
+        best
+    }
+
+    /// Runs Karger's contraction from a deterministic `seed` until a cut of at most `weight`
+    /// edges is found
+    ///
+    /// Useful when the size of the minimum cut is known in advance. Does not terminate if no
+    /// such cut exists.
+    pub fn karger_until(&self, seed: u64, weight: usize) -> Option<MinCut> {
+        if self.node_count() < 2 {
+            return None;
+        }
+        let mut rng = XorShift(seed | 1);
+        loop {
+            let cut = self.contract(&mut rng);
+            if cut.weight() <= weight {
+                return Some(cut);
+            }
+        }
+    }
+
+    /// Contracts edges in a random order until two groups of nodes remain
+    ///
+    /// This is equivalent to picking a uniformly random remaining edge at each step.
+    fn contract(&self, rng: &mut XorShift) -> MinCut {
+        let n = self.node_count();
+        let mut order = (0..self.edges.len()).collect::<Vec<_>>();
+        for i in (1..order.len()).rev() {
+            order.swap(i, rng.below(i + 1));
+        }
+
+        let mut groups = UnionFind::new(n);
+        for e in order {
+            if groups.count() == 2 {
+                break;
+            }
+            let (a, b) = self.edges[e];
+            groups.union(a, b);
+        }
+
+        // A disconnected graph may leave more groups: keep the one holding node 0 apart
+        let side = (0..n).map(|v| groups.connected(v, 0)).collect();
+        self.cut(side)
+    }
+
+    fn cut(&self, side: Vec<bool>) -> MinCut {
+        let edges = self
+            .edges
+            .iter()
+            .copied()
+            .filter(|&(a, b)| side[a] != side[b])
+            .collect();
+        MinCut { edges, side }
+    }
+}
+
+/// Small deterministic generator, so that the randomized cut needs no extra dependency
+struct XorShift(u64);
+
+impl XorShift {
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n as u64) as usize
+    }
+}
+
+#[test]
+fn min_cut_components() {
+    let graph = CutGraph::parse(
+        "jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr",
+    )
+    .unwrap();
+
+    let cut = graph.stoer_wagner().unwrap();
+    assert_eq!(cut.weight(), 3);
+    let (a, b) = cut.sizes();
+    assert_eq!(a * b, 54);
+    let mut names = cut
+        .edges
+        .iter()
+        .map(|&(a, b)| {
+            let mut pair = [graph.name(a), graph.name(b)];
+            pair.sort();
+            pair
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, [["bvb", "cmg"], ["hfx", "pzl"], ["jqt", "nvd"]]);
+
+    let random = graph.karger_until(1, 3).unwrap();
+    assert_eq!(random.weight(), 3);
+    assert_eq!(random.sizes().0 * random.sizes().1, 54);
+
+    // Two triangles joined by a single edge
+    let mut petgraph = petgraph::graph::UnGraph::<(), ()>::new_undirected();
+    let nodes = (0..6).map(|_| petgraph.add_node(())).collect::<Vec<_>>();
+    for (a, b) in [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)] {
+        petgraph.add_edge(nodes[a], nodes[b], ());
+    }
+    let cut = CutGraph::from_petgraph(&petgraph).stoer_wagner().unwrap();
+    assert_eq!(
+        (cut.sizes().0 * cut.sizes().1, cut.edges),
+        (9, vec![(2, 3)])
+    );
+
+    // A ring of six nodes around the hole left by a removed node
+    let mut stable = petgraph::stable_graph::StableUnGraph::<(), ()>::default();
+    let nodes = (0..7).map(|_| stable.add_node(())).collect::<Vec<_>>();
+    stable.remove_node(nodes[3]);
+    for (a, b) in [(0, 1), (1, 2), (2, 4), (4, 5), (5, 6), (6, 0)] {
+        stable.add_edge(nodes[a], nodes[b], ());
+    }
+    let graph = CutGraph::from_petgraph(&stable);
+    assert_eq!((graph.node_count(), graph.name(3)), (6, "4"));
+    assert_eq!(graph.stoer_wagner().unwrap().weight(), 2);
+}
@@ -0,0 +1,222 @@
+use crate::{Co2, Co3};
+
+/// Which lattice points count as neighbors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Connectivity {
+    /// Points differing by one along a single axis: 4 in 2D, 6 in 3D
+    Orthogonal,
+    /// Points differing by at most one along every axis: 8 in 2D, 26 in 3D
+    Full,
+}
+
+/// How neighbors beyond the edges of an area are treated
+///
+/// The area spans from zero up to but excluding `size` along each axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Bounds<C> {
+    /// Every neighbor is yielded, unless it overflows the coordinate type
+    Unbounded,
+    /// Neighbors outside of the area are skipped
+    Clipped(C),
+    /// Neighbors outside of the area wrap around to the opposite edge
+    Wrapping(C),
+}
+
+/// Integer type usable as a lattice axis
+pub trait Axis: Copy {
+    /// Returns `self + delta` within `0..size` if given, or `None` if out of range. An empty
+    /// range has no values to wrap around to.
+    fn offset(self, delta: i8, size: Option<Self>, wrap: bool) -> Option<Self>;
+
+    /// Returns the number of values from `self` up to and including `end`
+    fn span(self, end: Self) -> u128;
+}
+
+macro_rules! impl_axis {
+    ($($t:ty),*) => {$(
+        impl Axis for $t {
+            fn offset(self, delta: i8, size: Option<Self>, wrap: bool) -> Option<Self> {
+                let v = self as i128 + delta as i128;
+                match size {
+                    Some(size) if wrap => v
+                        .checked_rem_euclid(size as i128)
+                        .and_then(|v| <$t>::try_from(v).ok()),
+                    Some(size) => (0..size as i128)
+                        .contains(&v)
+                        .then(|| v as $t),
+                    None => <$t>::try_from(v).ok(),
+                }
+            }
+
+            fn span(self, end: Self) -> u128 {
+                (end as i128 - self as i128 + 1).max(0) as u128
+            }
+        }
+    )*};
+}
+
+impl_axis!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// Coordinate type with a fixed table of neighbor offsets
+pub trait Lattice: Copy {
+    /// Offset to a neighbor, with each component in -1..=1
+    type Offset: Copy + 'static;
+
+    fn offsets(connectivity: Connectivity) -> &'static [Self::Offset];
+
+    fn step(self, offset: Self::Offset, bounds: &Bounds<Self>) -> Option<Self>;
+}
+
+const CO2_ORTHOGONAL: [Co2<i8>; 4] = [Co2(-1, 0), Co2(0, 1), Co2(1, 0), Co2(0, -1)];
+const CO2_FULL: [Co2<i8>; 8] = [
+    Co2(-1, 0),
+    Co2(-1, 1),
+    Co2(0, 1),
+    Co2(1, 1),
+    Co2(1, 0),
+    Co2(1, -1),
+    Co2(0, -1),
+    Co2(-1, -1),
+];
+const CO3_ORTHOGONAL: [Co3<i8>; 6] = [
+    Co3(-1, 0, 0),
+    Co3(1, 0, 0),
+    Co3(0, -1, 0),
+    Co3(0, 1, 0),
+    Co3(0, 0, -1),
+    Co3(0, 0, 1),
+];
+const CO3_FULL: [Co3<i8>; 26] = {
+    let mut offsets = [Co3(0, 0, 0); 26];
+    let mut i = 0;
+    let mut n = 0;
+    while i < 27 {
+        // Skip the center, (0, 0, 0) at i = 13
+        if i != 13 {
+            offsets[n] = Co3(i / 9 - 1, i / 3 % 3 - 1, i % 3 - 1);
+            n += 1;
+        }
+        i += 1;
+    }
+    offsets
+};
+
+fn axis_bounds<T: Copy>(bounds: &Bounds<T>) -> (Option<T>, bool) {
+    match *bounds {
+        Bounds::Unbounded => (None, false),
+        Bounds::Clipped(size) => (Some(size), false),
+        Bounds::Wrapping(size) => (Some(size), true),
+    }
+}
+
+impl<T: Axis> Lattice for Co2<T> {
+    type Offset = Co2<i8>;
+
+    /// Starts north (-1 row) and proceeds clockwise
+    fn offsets(connectivity: Connectivity) -> &'static [Co2<i8>] {
+        match connectivity {
+            Connectivity::Orthogonal => &CO2_ORTHOGONAL,
+            Connectivity::Full => &CO2_FULL,
+        }
+    }
+
+    fn step(self, ofs: Co2<i8>, bounds: &Bounds<Self>) -> Option<Self> {
+        let (size, wrap) = axis_bounds(bounds);
+        Some(Co2(
+            self[0].offset(ofs[0], size.map(|s| s[0]), wrap)?,
+            self[1].offset(ofs[1], size.map(|s| s[1]), wrap)?,
+        ))
+    }
+}
+
+impl<T: Axis> Lattice for Co3<T> {
+    type Offset = Co3<i8>;
+
+    fn offsets(connectivity: Connectivity) -> &'static [Co3<i8>] {
+        match connectivity {
+            Connectivity::Orthogonal => &CO3_ORTHOGONAL,
+            Connectivity::Full => &CO3_FULL,
+        }
+    }
+
+    fn step(self, ofs: Co3<i8>, bounds: &Bounds<Self>) -> Option<Self> {
+        let (size, wrap) = axis_bounds(bounds);
+        Some(Co3(
+            self[0].offset(ofs[0], size.map(|s| s[0]), wrap)?,
+            self[1].offset(ofs[1], size.map(|s| s[1]), wrap)?,
+            self[2].offset(ofs[2], size.map(|s| s[2]), wrap)?,
+        ))
+    }
+}
+
+/// Iterator across the neighbors of a lattice point, yielding the offset to each neighbor with
+/// the neighbor itself
+#[derive(Debug, Clone)]
+pub struct Neighbors<C: Lattice> {
+    center: C,
+    bounds: Bounds<C>,
+    offsets: std::slice::Iter<'static, C::Offset>,
+}
+
+impl<C: Lattice> Iterator for Neighbors<C> {
+    type Item = (C::Offset, C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.offsets
+            .by_ref()
+            .find_map(|&ofs| Some((ofs, self.center.step(ofs, &self.bounds)?)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.offsets.len()))
+    }
+}
+
+/// Returns an iterator across the neighbors of `center`
+///
+/// With wrapping bounds on an axis shorter than three, the same neighbor may be yielded more than
+/// once, or be `center` itself. Bounds with an empty axis yield no neighbors.
+pub fn neighbors<C: Lattice>(
+    center: C,
+    connectivity: Connectivity,
+    bounds: Bounds<C>,
+) -> Neighbors<C> {
+    Neighbors {
+        center,
+        bounds,
+        offsets: C::offsets(connectivity).iter(),
+    }
+}
+
+#[test]
+fn lattice_neighbors() {
+    use Connectivity::*;
+
+    let corner = |bounds| {
+        neighbors(Co2(0usize, 0usize), Orthogonal, bounds)
+            .map(|(_, co)| co)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(corner(Bounds::Unbounded), [Co2(0, 1), Co2(1, 0)]);
+    assert_eq!(corner(Bounds::Clipped(Co2(1, 5))), [Co2(0, 1)]);
+    assert_eq!(
+        corner(Bounds::Wrapping(Co2(3, 4))),
+        [Co2(2, 0), Co2(0, 1), Co2(1, 0), Co2(0, 3)]
+    );
+    assert_eq!(corner(Bounds::Wrapping(Co2(0, 4))), []);
+
+    let (ofs, co) = neighbors(Co2(5i64, 5i64), Full, Bounds::Unbounded)
+        .nth(1)
+        .unwrap();
+    assert_eq!((ofs, co), (Co2(-1, 1), Co2(4, 6)));
+    assert_eq!(neighbors(Co2(5i64, 5), Full, Bounds::Unbounded).count(), 8);
+
+    let center = Co3(1u8, 1, 1);
+    assert_eq!(neighbors(center, Orthogonal, Bounds::Unbounded).count(), 6);
+    assert_eq!(neighbors(center, Full, Bounds::Unbounded).count(), 26);
+    assert_eq!(
+        neighbors(Co3(0u8, 0, 0), Full, Bounds::Clipped(Co3(2, 2, 2))).count(),
+        7
+    );
+    assert!(neighbors(center, Full, Bounds::Unbounded).all(|(_, co)| co != center));
+}
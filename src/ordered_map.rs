@@ -0,0 +1,284 @@
+use std::{
+    borrow::Borrow,
+    collections::{hash_map::RandomState, HashMap},
+    fmt,
+    hash::{BuildHasher, Hash},
+};
+
+const NIL: usize = usize::MAX;
+
+#[derive(Debug, Clone)]
+struct Slot<K, V> {
+    key: K,
+    value: V,
+    prev: usize,
+    next: usize,
+}
+
+/// Hash map that remembers insertion order
+///
+/// Entries are kept in a doubly linked list threaded through a slab, so insertion, replacement
+/// and removal are all O(1). Replacing the value of an existing key keeps its position.
+#[derive(Clone)]
+pub struct OrderedMap<K, V, S = RandomState> {
+    index: HashMap<K, usize, S>,
+    slots: Vec<Option<Slot<K, V>>>,
+    free: Vec<usize>,
+    head: usize,
+    tail: usize,
+}
+
+impl<K, V> OrderedMap<K, V> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<K, V> Default for OrderedMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, S> OrderedMap<K, V, S> {
+    pub fn with_hasher(hasher: S) -> Self {
+        OrderedMap {
+            index: HashMap::with_hasher(hasher),
+            slots: vec![],
+            free: vec![],
+            head: NIL,
+            tail: NIL,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.index.clear();
+        self.slots.clear();
+        self.free.clear();
+        self.head = NIL;
+        self.tail = NIL;
+    }
+
+    /// Returns an iterator across the entries in insertion order
+    ///
+    /// Use `enumerate` to get the position of each entry, counting from zero.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            slots: &self.slots,
+            cur: self.head,
+            remaining: self.len(),
+        }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(k, _)| k)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, v)| v)
+    }
+
+    /// Returns the first entry in insertion order
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.iter().next()
+    }
+
+    /// Returns the last entry in insertion order
+    pub fn last(&self) -> Option<(&K, &V)> {
+        self.slots
+            .get(self.tail)
+            .and_then(Option::as_ref)
+            .map(|slot| (&slot.key, &slot.value))
+    }
+
+    fn slot(&self, idx: usize) -> &Slot<K, V> {
+        self.slots[idx].as_ref().unwrap()
+    }
+
+    fn slot_mut(&mut self, idx: usize) -> &mut Slot<K, V> {
+        self.slots[idx].as_mut().unwrap()
+    }
+
+    fn unlink(&mut self, idx: usize) -> Slot<K, V> {
+        let slot = self.slots[idx].take().unwrap();
+
+        if slot.prev == NIL {
+            self.head = slot.next;
+        } else {
+            self.slot_mut(slot.prev).next = slot.next;
+        }
+        if slot.next == NIL {
+            self.tail = slot.prev;
+        } else {
+            self.slot_mut(slot.next).prev = slot.prev;
+        }
+        self.free.push(idx);
+
+        slot
+    }
+}
+
+impl<K, V, S> OrderedMap<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    /// Inserts `value` at `key`, returning the previous value if there was one
+    ///
+    /// A new key is placed last, an existing key keeps its position.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(&idx) = self.index.get(&key) {
+            return Some(std::mem::replace(&mut self.slot_mut(idx).value, value));
+        }
+
+        let slot = Slot {
+            key: key.clone(),
+            value,
+            prev: self.tail,
+            next: NIL,
+        };
+        let idx = if let Some(idx) = self.free.pop() {
+            self.slots[idx] = Some(slot);
+            idx
+        } else {
+            self.slots.push(Some(slot));
+            self.slots.len() - 1
+        };
+
+        if self.tail == NIL {
+            self.head = idx;
+        } else {
+            self.slot_mut(self.tail).next = idx;
+        }
+        self.tail = idx;
+        self.index.insert(key, idx);
+
+        None
+    }
+
+    /// Removes `key`, returning its value if it was present
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = self.index.remove(key)?;
+        Some(self.unlink(idx).value)
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.index.get(key).map(|&idx| &self.slot(idx).value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = *self.index.get(key)?;
+        Some(&mut self.slot_mut(idx).value)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.index.contains_key(key)
+    }
+
+    /// Returns the position of `key` in insertion order, counting from zero. This is O(n).
+    pub fn position<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if !self.contains_key(key) {
+            return None;
+        }
+        self.keys().position(|k| k.borrow() == key)
+    }
+}
+
+/// Iterator across the entries of an `OrderedMap` in insertion order
+pub struct Iter<'a, K, V> {
+    slots: &'a [Option<Slot<K, V>>],
+    cur: usize,
+    remaining: usize,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let slot = self.slots.get(self.cur)?.as_ref().unwrap();
+        self.cur = slot.next;
+        self.remaining -= 1;
+        Some((&slot.key, &slot.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+impl<'a, K, V, S> IntoIterator for &'a OrderedMap<K, V, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V, S> FromIterator<(K, V)> for OrderedMap<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    S: BuildHasher + Default,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::with_hasher(S::default());
+        for (k, v) in iter {
+            map.insert(k, v);
+        }
+        map
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> fmt::Debug for OrderedMap<K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[test]
+fn ordered_map_order() {
+    let mut map = OrderedMap::new();
+    map.insert("rn", 1);
+    map.insert("cm", 2);
+    map.insert("qp", 3);
+    assert_eq!(map.insert("rn", 4), Some(1));
+    assert_eq!(map.remove("cm"), Some(2));
+    map.insert("pc", 5);
+
+    assert_eq!(
+        map.iter().collect::<Vec<_>>(),
+        [(&"rn", &4), (&"qp", &3), (&"pc", &5)]
+    );
+    assert_eq!(map.position("pc"), Some(2));
+    assert_eq!(map.last(), Some((&"pc", &5)));
+}
@@ -0,0 +1,242 @@
+//! Pipe mazes with a hidden start tile
+use crate::{Co2, Dir, Grid};
+use anyhow::{bail, Context};
+
+/// A tile in the pipe maze
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pipe {
+    /// `|`
+    Vertical,
+    /// `-`
+    Horizontal,
+    /// `L`
+    NorthEast,
+    /// `J`
+    NorthWest,
+    /// `7`
+    SouthWest,
+    /// `F`
+    SouthEast,
+    /// `.`
+    Ground,
+    /// `S`, the start tile with an unknown pipe under it
+    Start,
+}
+
+impl Pipe {
+    pub fn from_char(c: char) -> Option<Pipe> {
+        Some(match c {
+            '|' => Pipe::Vertical,
+            '-' => Pipe::Horizontal,
+            'L' => Pipe::NorthEast,
+            'J' => Pipe::NorthWest,
+            '7' => Pipe::SouthWest,
+            'F' => Pipe::SouthEast,
+            '.' => Pipe::Ground,
+            'S' => Pipe::Start,
+            _ => return None,
+        })
+    }
+
+    pub fn as_char(&self) -> char {
+        match self {
+            Pipe::Vertical => '|',
+            Pipe::Horizontal => '-',
+            Pipe::NorthEast => 'L',
+            Pipe::NorthWest => 'J',
+            Pipe::SouthWest => '7',
+            Pipe::SouthEast => 'F',
+            Pipe::Ground => '.',
+            Pipe::Start => 'S',
+        }
+    }
+
+    /// Returns the directions the pipe connects to. The start tile connects nowhere until its
+    /// pipe is known.
+    pub fn dirs(&self) -> &'static [Dir] {
+        use Dir::*;
+        match self {
+            Pipe::Vertical => &[North, South],
+            Pipe::Horizontal => &[East, West],
+            Pipe::NorthEast => &[North, East],
+            Pipe::NorthWest => &[North, West],
+            Pipe::SouthWest => &[South, West],
+            Pipe::SouthEast => &[South, East],
+            Pipe::Ground | Pipe::Start => &[],
+        }
+    }
+
+    pub fn connects(&self, dir: Dir) -> bool {
+        self.dirs().contains(&dir)
+    }
+
+    /// Returns the pipe connecting `a` and `b`, if they differ
+    pub fn from_dirs(a: Dir, b: Dir) -> Option<Pipe> {
+        [
+            Pipe::Vertical,
+            Pipe::Horizontal,
+            Pipe::NorthEast,
+            Pipe::NorthWest,
+            Pipe::SouthWest,
+            Pipe::SouthEast,
+        ]
+        .into_iter()
+        .find(|pipe| a != b && pipe.connects(a) && pipe.connects(b))
+    }
+}
+
+/// Where a tile lies relative to the main loop
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    Loop,
+    Inside,
+    Outside,
+}
+
+/// Pipe maze where the pipe under the start tile has been inferred
+#[derive(Debug, Clone)]
+pub struct PipeMaze {
+    grid: Grid<Pipe>,
+    start: Co2<usize>,
+    main_loop: Vec<Co2<usize>>,
+}
+
+impl PipeMaze {
+    /// Parses the maze and infers the pipe under `S` from its neighbors that connect to it
+    ///
+    /// Neighbors may connect to `S` without being part of the loop, so the pipe is the first one
+    /// connecting two of them through which a loop leads back to `S`.
+    pub fn parse(input: &str) -> anyhow::Result<Self> {
+        let chars = Grid::try_parse(input)?;
+        if let Some((co, c)) = chars.iter().find(|(_, c)| Pipe::from_char(**c).is_none()) {
+            bail!("unknown tile {c:?} at {co:?}");
+        }
+        let mut grid = chars.map(|&c| Pipe::from_char(c).unwrap());
+
+        let start = grid.find(&Pipe::Start).context("no start tile")?;
+        let dirs = Dir::ALL
+            .into_iter()
+            .filter(|&dir| {
+                grid.step(start, dir)
+                    .is_some_and(|co| grid[co].connects(dir.reverse()))
+            })
+            .collect::<Vec<_>>();
+        for (i, &a) in dirs.iter().enumerate() {
+            for &b in &dirs[i + 1..] {
+                grid[start] = Pipe::from_dirs(a, b).unwrap();
+                if let Some(main_loop) = walk_loop(&grid, start) {
+                    return Ok(PipeMaze {
+                        grid,
+                        start,
+                        main_loop,
+                    });
+                }
+            }
+        }
+        bail!(
+            "no loop through the start tile among its {} connecting neighbors",
+            dirs.len()
+        )
+    }
+
+    pub fn start(&self) -> Co2<usize> {
+        self.start
+    }
+
+    /// Returns the grid with the start tile replaced by its pipe
+    pub fn grid(&self) -> &Grid<Pipe> {
+        &self.grid
+    }
+
+    /// Returns the tiles of the loop through the start tile, in order starting from it
+    pub fn main_loop(&self) -> &[Co2<usize>] {
+        &self.main_loop
+    }
+
+    /// Classifies every tile as part of the main loop, inside or outside of it
+    ///
+    /// Scans each row while counting crossings of the loop. Only tiles connecting north count as
+    /// crossings, so that squeezing between parallel pipes is handled correctly: `L-7` crosses
+    /// once, `L-J` does not cross at all.
+    pub fn classify(&self) -> Grid<Side> {
+        let mut sides = Grid::new(self.grid.rows(), self.grid.cols(), Side::Outside);
+        for &co in self.main_loop() {
+            sides[co] = Side::Loop;
+        }
+
+        for row in 0..self.grid.rows() {
+            let mut inside = false;
+            for col in 0..self.grid.cols() {
+                let co = Co2(row, col);
+                if sides[co] == Side::Loop {
+                    if self.grid[co].connects(Dir::North) {
+                        inside = !inside;
+                    }
+                } else if inside {
+                    sides[co] = Side::Inside;
+                }
+            }
+        }
+
+        sides
+    }
+
+    /// Counts the tiles enclosed by the main loop
+    pub fn count_inside(&self) -> usize {
+        self.classify()
+            .cells()
+            .iter()
+            .filter(|&&side| side == Side::Inside)
+            .count()
+    }
+}
+
+/// Follows the pipes from `start` until they lead back to it, returning the tiles passed in order.
+/// Returns `None` if they lead out of the grid or into a tile not connecting back.
+fn walk_loop(grid: &Grid<Pipe>, start: Co2<usize>) -> Option<Vec<Co2<usize>>> {
+    let mut path = vec![start];
+    let mut dir = grid[start].dirs()[0];
+    let mut cur = start;
+    loop {
+        cur = grid.step(cur, dir)?;
+        if cur == start {
+            return grid[start].connects(dir.reverse()).then_some(path);
+        }
+        if !grid[cur].connects(dir.reverse()) {
+            return None;
+        }
+        path.push(cur);
+
+        // Continue through the other end of the pipe
+        let from = dir.reverse();
+        dir = *grid[cur].dirs().iter().find(|&&d| d != from)?;
+    }
+}
+
+#[test]
+fn pipe_maze_squeeze() {
+    let maze = PipeMaze::parse(
+        "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........",
+    )
+    .unwrap();
+
+    assert_eq!(maze.grid()[maze.start()], Pipe::SouthEast);
+    assert_eq!(maze.main_loop().len(), 44);
+    assert_eq!(maze.main_loop()[..2], [Co2(1, 1), Co2(2, 1)]);
+    assert_eq!(maze.count_inside(), 4);
+
+    // Stray pipes next to the start tile are not part of the loop
+    let maze = PipeMaze::parse(".|...\n-S-7.\n.|.|.\n.L-J.").unwrap();
+    assert_eq!(maze.grid()[maze.start()], Pipe::SouthEast);
+    assert_eq!(maze.main_loop().len(), 8);
+    assert!(PipeMaze::parse(".|...\n-S-7.\n.|.|.\n.L-..").is_err());
+    assert!(PipeMaze::parse("S-\n|").is_err());
+}
@@ -0,0 +1,176 @@
+use crate::{Axis, Co, Difference};
+
+/// Axis-aligned box spanning from `min` to `max` inclusive along each axis
+///
+/// A box always contains at least one point: operations that could produce an empty box, such
+/// as [`Aabb::intersection`], return an `Option` instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Aabb<T, const N: usize> {
+    min: Co<T, N>,
+    max: Co<T, N>,
+}
+
+/// Rectangle of `Co2` points
+pub type Rect<T> = Aabb<T, 2>;
+
+/// Cuboid of `Co3` points
+pub type Cuboid<T> = Aabb<T, 3>;
+
+impl<T: Copy + PartialOrd, const N: usize> Aabb<T, N> {
+    /// Creates the box with `a` and `b` as opposite corners
+    pub fn new(a: Co<T, N>, b: Co<T, N>) -> Self {
+        let pick = |low: bool| {
+            Co::new(std::array::from_fn(|axis| {
+                if (a[axis] <= b[axis]) == low {
+                    a[axis]
+                } else {
+                    b[axis]
+                }
+            }))
+        };
+        Aabb {
+            min: pick(true),
+            max: pick(false),
+        }
+    }
+
+    /// Creates the smallest box containing every point, or `None` if there are no points
+    pub fn bounding(points: impl IntoIterator<Item = Co<T, N>>) -> Option<Self> {
+        points
+            .into_iter()
+            .map(|co| Aabb { min: co, max: co })
+            .reduce(|a, b| a.union_bounds(&b))
+    }
+
+    pub fn min(&self) -> Co<T, N> {
+        self.min
+    }
+
+    pub fn max(&self) -> Co<T, N> {
+        self.max
+    }
+
+    pub fn contains(&self, co: Co<T, N>) -> bool {
+        (0..N).all(|axis| self.min[axis] <= co[axis] && co[axis] <= self.max[axis])
+    }
+
+    /// Returns true if the boxes share at least one point
+    pub fn intersects(&self, other: &Self) -> bool {
+        (0..N).all(|axis| self.min[axis] <= other.max[axis] && other.min[axis] <= self.max[axis])
+    }
+
+    /// Returns the box of points in both boxes, or `None` if they do not intersect
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        self.intersects(other).then(|| Aabb {
+            min: Co::new(std::array::from_fn(|axis| {
+                partial_max(self.min[axis], other.min[axis])
+            })),
+            max: Co::new(std::array::from_fn(|axis| {
+                partial_min(self.max[axis], other.max[axis])
+            })),
+        })
+    }
+
+    /// Returns the smallest box containing both boxes
+    pub fn union_bounds(&self, other: &Self) -> Self {
+        Aabb {
+            min: Co::new(std::array::from_fn(|axis| {
+                partial_min(self.min[axis], other.min[axis])
+            })),
+            max: Co::new(std::array::from_fn(|axis| {
+                partial_max(self.max[axis], other.max[axis])
+            })),
+        }
+    }
+}
+
+impl<T: Difference + PartialOrd, const N: usize> Aabb<T, N> {
+    /// Moves the box by `delta`, returning `None` if a corner would not fit in `T`
+    pub fn translate(&self, delta: Co<T::Signed, N>) -> Option<Self>
+    where
+        T::Signed: Copy,
+    {
+        Some(Aabb {
+            min: self.min.checked_offset(delta)?,
+            max: self.max.checked_offset(delta)?,
+        })
+    }
+}
+
+impl<T: Axis + PartialOrd, const N: usize> Aabb<T, N> {
+    /// Number of points along each axis
+    pub fn extent(&self) -> [u128; N] {
+        std::array::from_fn(|axis| self.min[axis].span(self.max[axis]))
+    }
+
+    /// Number of points in the box
+    pub fn volume(&self) -> u128 {
+        self.extent().iter().product()
+    }
+
+    /// Returns an iterator across the points in the box, the last axis changing fastest. For a
+    /// `Rect` of (row, col) points, that is row-major order.
+    pub fn points(&self) -> impl Iterator<Item = Co<T, N>> {
+        let Aabb { min, max } = *self;
+        std::iter::successors(Some(min), move |&co| {
+            let mut next = co;
+            for axis in (0..N).rev() {
+                if next[axis] < max[axis] {
+                    next[axis] = next[axis].offset(1, None, false)?;
+                    return Some(next);
+                }
+                next[axis] = min[axis];
+            }
+            None
+        })
+    }
+}
+
+fn partial_min<T: PartialOrd>(a: T, b: T) -> T {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+fn partial_max<T: PartialOrd>(a: T, b: T) -> T {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+#[test]
+fn aabb_ops() {
+    use crate::{Co2, Co3};
+
+    let a = Rect::new(Co2(3, 1), Co2(1, 4));
+    assert_eq!((a.min(), a.max()), (Co2(1, 1), Co2(3, 4)));
+    assert!(a.contains(Co2(2, 4)) && !a.contains(Co2(0, 2)));
+    assert_eq!(a.volume(), 12);
+
+    let b = Rect::new(Co2(3, 4), Co2(5, 5));
+    assert_eq!(a.intersection(&b), Some(Rect::new(Co2(3, 4), Co2(3, 4))));
+    assert!(!a.intersects(&Rect::new(Co2(4, 0), Co2(4, 9))));
+    assert_eq!(a.union_bounds(&b), Rect::new(Co2(1, 1), Co2(5, 5)));
+    assert_eq!(
+        Rect::bounding([Co2(2, 7), Co2(-1, 3), Co2(0, 8)]),
+        Some(Rect::new(Co2(-1, 3), Co2(2, 8)))
+    );
+
+    let c = Cuboid::new(Co3(0usize, 0, 1), Co3(1, 0, 2));
+    assert_eq!(
+        c.points().collect::<Vec<_>>(),
+        [Co3(0, 0, 1), Co3(0, 0, 2), Co3(1, 0, 1), Co3(1, 0, 2)]
+    );
+    assert_eq!(
+        c.translate(Co3(0, 0, -1)).map(|c| c.min()),
+        Some(Co3(0, 0, 0))
+    );
+    assert_eq!(c.translate(Co3(0, -1, 0)), None);
+
+    let area = Rect::new(Co2(7., 7.), Co2(27., 27.));
+    assert!(area.contains(Co2(14.333, 15.333)));
+}
@@ -0,0 +1,94 @@
+use crate::{Co2, Grid};
+
+/// Axis of reflection in a pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Mirror {
+    /// Horizontal line with this many rows above it
+    Horizontal(usize),
+    /// Vertical line with this many columns left of it
+    Vertical(usize),
+}
+
+impl Mirror {
+    /// Returns the number of columns left of a vertical line, or 100 times the number of rows
+    /// above a horizontal line
+    pub fn summary(&self) -> usize {
+        match self {
+            Mirror::Horizontal(rows) => rows * 100,
+            Mirror::Vertical(cols) => *cols,
+        }
+    }
+}
+
+/// Packs each row of `pattern` into a bitmask, first column in the lowest bit
+///
+/// Panics if the pattern is wider than 64 cells.
+pub fn pack_rows(pattern: &Grid<bool>) -> Vec<u64> {
+    assert!(pattern.cols() <= 64, "pattern too wide to pack");
+    pattern
+        .iter_rows()
+        .map(|row| {
+            row.iter()
+                .enumerate()
+                .fold(0, |mask, (col, &b)| mask | (b as u64) << col)
+        })
+        .collect()
+}
+
+/// Packs each column of `pattern` into a bitmask, first row in the lowest bit
+///
+/// Panics if the pattern is taller than 64 cells.
+pub fn pack_cols(pattern: &Grid<bool>) -> Vec<u64> {
+    assert!(pattern.rows() <= 64, "pattern too tall to pack");
+    (0..pattern.cols())
+        .map(|col| {
+            (0..pattern.rows()).fold(0, |mask, row| mask | (pattern[Co2(row, col)] as u64) << row)
+        })
+        .collect()
+}
+
+/// Returns every axis between two lines of `lines` where the reflected lines differ in exactly
+/// `budget` cells
+fn axes(lines: &[u64], budget: u32) -> impl Iterator<Item = usize> + '_ {
+    (1..lines.len()).filter(move |&axis| {
+        let mut mismatch = 0;
+        for (above, below) in lines[..axis].iter().rev().zip(&lines[axis..]) {
+            mismatch += (above ^ below).count_ones();
+            if mismatch > budget {
+                return false;
+            }
+        }
+        mismatch == budget
+    })
+}
+
+/// Finds every horizontal and vertical mirror in `pattern` that is off by exactly `budget` cells
+///
+/// A budget of zero finds the perfect mirrors. A budget of one finds the mirrors that appear
+/// when exactly one smudge is cleaned.
+pub fn find_mirrors(pattern: &Grid<bool>, budget: u32) -> Vec<Mirror> {
+    let rows = pack_rows(pattern);
+    let cols = pack_cols(pattern);
+
+    axes(&rows, budget)
+        .map(Mirror::Horizontal)
+        .chain(axes(&cols, budget).map(Mirror::Vertical))
+        .collect()
+}
+
+#[test]
+fn find_mirrors_smudge() {
+    let pattern = Grid::parse(
+        "#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.",
+    )
+    .map(|&c| c == '#');
+
+    assert_eq!(find_mirrors(&pattern, 0), [Mirror::Vertical(5)]);
+    assert_eq!(find_mirrors(&pattern, 1), [Mirror::Horizontal(3)]);
+}
@@ -0,0 +1,221 @@
+//! Connected regions of grid cells
+use crate::{neighbors, Bounds, Co2, Connectivity, Grid, GridBitSet, Rect};
+
+/// Connected set of matching cells
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    cells: Vec<Co2<usize>>,
+    perimeter: usize,
+    bounds: Rect<usize>,
+    touches_border: bool,
+}
+
+impl Region {
+    /// Returns the cells of the region in the order they were found, starting from the first one
+    /// in row-major order
+    pub fn cells(&self) -> &[Co2<usize>] {
+        &self.cells
+    }
+
+    /// Number of cells in the region
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Number of cell edges between the region and anything else, including the grid edges
+    pub fn perimeter(&self) -> usize {
+        self.perimeter
+    }
+
+    pub fn bounds(&self) -> Rect<usize> {
+        self.bounds
+    }
+
+    /// Returns true if a cell of the region lies on the edge of the grid
+    pub fn touches_border(&self) -> bool {
+        self.touches_border
+    }
+}
+
+/// Regions of a grid, with the region of each cell
+#[derive(Debug, Clone)]
+pub struct Labels {
+    labels: Grid<Option<usize>>,
+    regions: Vec<Region>,
+}
+
+impl Labels {
+    /// Returns the index of the region containing `co`, or `None` if the cell did not match
+    pub fn label(&self, co: Co2<usize>) -> Option<usize> {
+        self.labels[co]
+    }
+
+    pub fn labels(&self) -> &Grid<Option<usize>> {
+        &self.labels
+    }
+
+    pub fn region_at(&self, co: Co2<usize>) -> Option<&Region> {
+        self.label(co).map(|label| &self.regions[label])
+    }
+
+    /// Returns the regions, ordered by their first cell in row-major order
+    pub fn regions(&self) -> &[Region] {
+        &self.regions
+    }
+}
+
+/// Labels the connected regions of cells for which `matches` returns true
+///
+/// With `Connectivity::Full`, cells touching only at a corner belong to the same region. The
+/// perimeter counts the orthogonal edges either way.
+pub fn label<T>(
+    grid: &Grid<T>,
+    connectivity: Connectivity,
+    matches: impl Fn(Co2<usize>, &T) -> bool,
+) -> Labels {
+    let (rows, cols) = (grid.rows(), grid.cols());
+    let bounds = Bounds::Clipped(Co2(rows, cols));
+    let matching = Grid::from_fn(rows, cols, |co| matches(co, &grid[co]));
+    let mut labels = Grid::new(rows, cols, None);
+    let mut regions = vec![];
+
+    for (start, _) in matching.iter().filter(|(_, &m)| m) {
+        if labels[start].is_some() {
+            continue;
+        }
+        let label = Some(regions.len());
+        labels[start] = label;
+        let mut cells = vec![start];
+        let mut perimeter = 0;
+
+        // The cells vector doubles as the search stack
+        let mut next_idx = 0;
+        while let Some(&co) = cells.get(next_idx) {
+            next_idx += 1;
+            let edges = neighbors(co, Connectivity::Orthogonal, bounds)
+                .filter(|&(_, next)| matching[next])
+                .count();
+            perimeter += 4 - edges;
+            for (_, next) in neighbors(co, connectivity, bounds) {
+                if matching[next] && labels[next].is_none() {
+                    labels[next] = label;
+                    cells.push(next);
+                }
+            }
+        }
+
+        let touches_border = cells
+            .iter()
+            .any(|co| co[0] == 0 || co[1] == 0 || co[0] == rows - 1 || co[1] == cols - 1);
+        regions.push(Region {
+            bounds: Rect::bounding(cells.iter().copied()).unwrap(),
+            cells,
+            perimeter,
+            touches_border,
+        });
+    }
+
+    Labels { labels, regions }
+}
+
+/// Returns the cells reachable from `seeds` through cells for which `passable` returns true.
+/// Seeds are filled whether or not they are passable.
+///
+/// If `pad` is true, the grid is surrounded by a ring of passable cells that is filled along with
+/// the seeds. The result then includes every cell reachable from outside of the grid.
+pub fn flood_fill<T>(
+    grid: &Grid<T>,
+    seeds: impl IntoIterator<Item = Co2<usize>>,
+    connectivity: Connectivity,
+    pad: bool,
+    passable: impl Fn(Co2<usize>, &T) -> bool,
+) -> GridBitSet {
+    // Works in padded coordinates, which are shifted by one if there is padding
+    let shift = pad as usize;
+    let (rows, cols) = (grid.rows() + 2 * shift, grid.cols() + 2 * shift);
+    let inner = |co: Co2<usize>| {
+        let co = Co2(co[0].wrapping_sub(shift), co[1].wrapping_sub(shift));
+        grid.contains(co).then_some(co)
+    };
+
+    let mut filled = GridBitSet::new(rows, cols);
+    let mut stack = seeds
+        .into_iter()
+        .map(|co| Co2(co[0] + shift, co[1] + shift))
+        .collect::<Vec<_>>();
+    if pad {
+        stack.extend(
+            Rect::new(Co2(0, 0), Co2(rows - 1, cols - 1))
+                .points()
+                .filter(|&co| inner(co).is_none()),
+        );
+    }
+    for &co in &stack {
+        filled.insert(co);
+    }
+
+    let bounds = Bounds::Clipped(Co2(rows, cols));
+    while let Some(co) = stack.pop() {
+        for (_, next) in neighbors(co, connectivity, bounds) {
+            if !filled.contains(next)
+                && inner(next).is_none_or(|inner| passable(inner, &grid[inner]))
+            {
+                filled.insert(next);
+                stack.push(next);
+            }
+        }
+    }
+
+    let mut result = GridBitSet::for_grid(grid);
+    for co in filled.iter().filter_map(inner) {
+        result.insert(co);
+    }
+    result
+}
+
+#[test]
+fn region_metrics() {
+    let grid = Grid::parse(
+        "AAAA\n\
+         BBCD\n\
+         BBCC\n\
+         EEEC",
+    );
+    let labels = label(&grid, Connectivity::Orthogonal, |_, &c| c == 'C');
+    assert_eq!(labels.regions().len(), 1);
+    assert_eq!(labels.label(Co2(1, 3)), None);
+    let c = labels.region_at(Co2(1, 2)).unwrap();
+    assert_eq!((c.area(), c.perimeter()), (4, 10));
+    assert_eq!(c.bounds(), Rect::new(Co2(1, 2), Co2(3, 3)));
+    assert!(c.touches_border());
+
+    // Diagonal neighbors join with full connectivity
+    let grid = Grid::parse("#..\n.#.\n..#");
+    let is_wall = |_, &c: &char| c == '#';
+    assert_eq!(
+        label(&grid, Connectivity::Orthogonal, is_wall)
+            .regions()
+            .len(),
+        3
+    );
+    let full = label(&grid, Connectivity::Full, is_wall);
+    assert_eq!(full.regions()[0].area(), 3);
+    assert_eq!(full.label(Co2(2, 2)), Some(0));
+}
+
+#[test]
+fn flood_fill_outside() {
+    let grid = Grid::parse(
+        ".....\n\
+         .###.\n\
+         .#.#.\n\
+         .###.",
+    );
+    let open = |_, &c: &char| c == '.';
+    let outside = flood_fill(&grid, [], Connectivity::Orthogonal, true, open);
+    assert_eq!(outside.count(), 11);
+    assert!(!outside.contains(Co2(2, 2)));
+
+    let inside = flood_fill(&grid, [Co2(2, 2)], Connectivity::Orthogonal, false, open);
+    assert_eq!(inside.iter().collect::<Vec<_>>(), [Co2(2, 2)]);
+}
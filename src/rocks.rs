@@ -0,0 +1,240 @@
+use crate::{Dir, Grid};
+use std::collections::HashMap;
+
+/// Largest number of rows or columns a `RockBoard` can have
+pub const MAX_BOARD_SIDE: usize = 128;
+
+/// Transposes a square bit matrix in place, where bit `j` of `m[i]` is the element at (i, j)
+///
+/// Swaps the off-diagonal blocks of halving size, so it takes 7 passes over the 128 words.
+fn transpose(m: &mut [u128; MAX_BOARD_SIDE]) {
+    let mut j = 64;
+    let mut mask: u128 = u64::MAX as u128;
+    while j != 0 {
+        for k in (0..MAX_BOARD_SIDE).filter(|k| k & j == 0) {
+            let t = ((m[k] >> j) ^ m[k + j]) & mask;
+            m[k] ^= t << j;
+            m[k + j] ^= t;
+        }
+        j >>= 1;
+        mask ^= mask << j;
+    }
+}
+
+/// Returns a mask of `n` bits starting from bit `start`
+fn bits(start: usize, n: usize) -> u128 {
+    if n == 0 {
+        0
+    } else {
+        (u128::MAX >> (128 - n)) << start
+    }
+}
+
+/// Runs of free cells between cube rocks along each line, as (start, length)
+fn segments(cubes: &[u128; MAX_BOARD_SIDE], len: usize) -> Vec<Vec<(usize, usize)>> {
+    cubes
+        .iter()
+        .map(|&line| {
+            let mut segs = vec![];
+            let mut start = 0;
+            for idx in 0..=len {
+                if idx == len || line & 1 << idx != 0 {
+                    if idx > start {
+                        segs.push((start, idx - start));
+                    }
+                    start = idx + 1;
+                }
+            }
+            segs
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    /// `round[row]` has bit `col` set for each round rock
+    Rows,
+    /// `round[col]` has bit `row` set for each round rock
+    Cols,
+}
+
+/// Round (`O`) and cube (`#`) rocks on a platform, stored as bitsets along rows or columns
+///
+/// Tilting rolls every round rock as far as it goes in one direction. Each line is split into
+/// segments between the cube rocks and the rocks in a segment are counted and packed to one end
+/// with a few word operations. North and south tilts work on columns, west and east on rows; the
+/// round rocks are transposed between the two as needed.
+#[derive(Debug, Clone)]
+pub struct RockBoard {
+    rows: usize,
+    cols: usize,
+    layout: Layout,
+    round: [u128; MAX_BOARD_SIDE],
+    /// Cube rocks row by row
+    cubes: [u128; MAX_BOARD_SIDE],
+    row_segments: Vec<Vec<(usize, usize)>>,
+    col_segments: Vec<Vec<(usize, usize)>>,
+}
+
+impl RockBoard {
+    /// Reads the round rocks `O` and cube rocks `#` from `grid`, every other cell is empty
+    ///
+    /// Panics if the grid has more than `MAX_BOARD_SIDE` rows or columns.
+    pub fn from_grid(grid: &Grid<char>) -> Self {
+        let (rows, cols) = (grid.rows(), grid.cols());
+        assert!(
+            rows <= MAX_BOARD_SIDE && cols <= MAX_BOARD_SIDE,
+            "board too large"
+        );
+
+        let mut round = [0; MAX_BOARD_SIDE];
+        let mut cubes = [0; MAX_BOARD_SIDE];
+        for (co, c) in grid.iter() {
+            match c {
+                'O' => round[co[0]] |= 1 << co[1],
+                '#' => cubes[co[0]] |= 1 << co[1],
+                _ => {}
+            }
+        }
+
+        let row_segments = segments(&cubes, cols);
+        let mut col_cubes = cubes;
+        transpose(&mut col_cubes);
+        let col_segments = segments(&col_cubes, rows);
+
+        RockBoard {
+            rows,
+            cols,
+            layout: Layout::Rows,
+            round,
+            cubes,
+            row_segments,
+            col_segments,
+        }
+    }
+
+    fn set_layout(&mut self, layout: Layout) {
+        if self.layout != layout {
+            transpose(&mut self.round);
+            self.layout = layout;
+        }
+    }
+
+    /// Rolls all round rocks as far as they go towards `dir`
+    pub fn tilt(&mut self, dir: Dir) {
+        let segments = if dir.is_vertical() {
+            self.set_layout(Layout::Cols);
+            &self.col_segments
+        } else {
+            self.set_layout(Layout::Rows);
+            &self.row_segments
+        };
+        // Towards row or column zero
+        let to_low = dir == Dir::North || dir == Dir::West;
+
+        for (line, segs) in self.round.iter_mut().zip(segments) {
+            for &(start, len) in segs {
+                let seg = bits(start, len);
+                let n = (*line & seg).count_ones() as usize;
+                let packed = if to_low {
+                    bits(start, n)
+                } else {
+                    bits(start + len - n, n)
+                };
+                *line = (*line & !seg) | packed;
+            }
+        }
+    }
+
+    /// Tilts north, west, south and east, in that order
+    pub fn spin(&mut self) {
+        for dir in [Dir::North, Dir::West, Dir::South, Dir::East] {
+            self.tilt(dir);
+        }
+    }
+
+    /// Spins `n` times, skipping ahead once the board returns to an earlier state
+    pub fn spin_n(&mut self, n: usize) {
+        let mut seen = HashMap::new();
+        for idx in 0..n {
+            if let Some(prev) = seen.insert(self.state(), idx) {
+                let period = idx - prev;
+                for _ in 0..(n - idx) % period {
+                    self.spin();
+                }
+                return;
+            }
+            self.spin();
+        }
+    }
+
+    /// Returns the round rocks row by row, which identifies the state of the board exactly
+    pub fn state(&self) -> Vec<u128> {
+        let mut round = self.round;
+        if self.layout == Layout::Cols {
+            transpose(&mut round);
+        }
+        round[..self.rows].to_vec()
+    }
+
+    /// Returns the total load on the north support beams
+    pub fn north_load(&self) -> usize {
+        self.state()
+            .into_iter()
+            .enumerate()
+            .map(|(row, line)| line.count_ones() as usize * (self.rows - row))
+            .sum()
+    }
+
+    pub fn to_grid(&self) -> Grid<char> {
+        let round = self.state();
+        Grid::from_fn(self.rows, self.cols, |co| {
+            if round[co[0]] & 1 << co[1] != 0 {
+                'O'
+            } else if self.cubes[co[0]] & 1 << co[1] != 0 {
+                '#'
+            } else {
+                '.'
+            }
+        })
+    }
+}
+
+#[test]
+fn rock_board_spin() {
+    let grid = Grid::parse(
+        "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....",
+    );
+    let mut board = RockBoard::from_grid(&grid);
+    board.tilt(Dir::North);
+    assert_eq!(board.north_load(), 136);
+
+    let mut board = RockBoard::from_grid(&grid);
+    board.spin();
+    assert_eq!(
+        board.to_grid().to_string(),
+        ".....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#....
+"
+    );
+
+    board.spin_n(1_000_000_000 - 1);
+    assert_eq!(board.north_load(), 64);
+}
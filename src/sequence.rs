@@ -0,0 +1,141 @@
+//! Polynomial sequences fitted to integer samples
+//!
+//! Values are computed in `i128` with overflow checks, returning `None` on overflow.
+
+/// Polynomial of minimal degree through samples taken at x = 0, 1, 2, ..., stored as the leading
+/// entries of its forward difference table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    /// The k-th entry is the k-th forward difference at x = 0
+    diffs: Vec<i128>,
+    samples: usize,
+}
+
+impl Polynomial {
+    /// Fits the polynomial of lowest degree through `samples`, the first at x = 0
+    ///
+    /// Returns `None` if there are no samples, or if the difference table overflows.
+    pub fn fit(samples: &[i64]) -> Option<Self> {
+        let first = *samples.first()?;
+        let mut row = samples.iter().map(|&v| v as i128).collect::<Vec<_>>();
+        let mut diffs = vec![first as i128];
+        while row.len() > 1 && row.iter().any(|&v| v != row[0]) {
+            row = row
+                .windows(2)
+                .map(|w| w[1].checked_sub(w[0]))
+                .collect::<Option<_>>()?;
+            diffs.push(row[0]);
+        }
+        Some(Polynomial {
+            diffs,
+            samples: samples.len(),
+        })
+    }
+
+    /// Degree of the polynomial, zero for a constant
+    pub fn degree(&self) -> usize {
+        self.diffs.len() - 1
+    }
+
+    /// Returns true if there were more samples than needed to fit the polynomial, so that the
+    /// degree is backed by the data rather than limited by the number of samples
+    pub fn is_determined(&self) -> bool {
+        self.samples > self.diffs.len()
+    }
+
+    /// Returns the value at `x` by Newton's forward difference formula
+    pub fn eval(&self, x: i64) -> Option<i128> {
+        let x = x as i128;
+        // Generalized binomial coefficient C(x, k), exact for negative x too
+        let mut binomial = 1i128;
+        let mut sum = 0i128;
+        for (k, &diff) in self.diffs.iter().enumerate() {
+            if k > 0 {
+                binomial = binomial.checked_mul(x - (k as i128 - 1))? / k as i128;
+            }
+            sum = sum.checked_add(diff.checked_mul(binomial)?)?;
+        }
+        Some(sum)
+    }
+
+    /// Returns the value `k` steps after the last sample
+    pub fn forward(&self, k: i64) -> Option<i128> {
+        self.eval((self.samples as i64 - 1).checked_add(k)?)
+    }
+
+    /// Returns the value `k` steps before the first sample
+    pub fn backward(&self, k: i64) -> Option<i128> {
+        self.eval(k.checked_neg()?)
+    }
+}
+
+/// Returns the value at `x` of the polynomial through `points` by exact Lagrange interpolation
+///
+/// The x coordinates may be spaced arbitrarily but must be distinct. Returns `None` if they are
+/// not, on overflow, or if the value is not an integer.
+pub fn interpolate(points: &[(i64, i64)], x: i64) -> Option<i128> {
+    // Sum of fractions, kept in lowest terms
+    let (mut num, mut den) = (0i128, 1i128);
+    for (i, &(xi, yi)) in points.iter().enumerate() {
+        let (mut term_num, mut term_den) = (yi as i128, 1i128);
+        for (j, &(xj, _)) in points.iter().enumerate() {
+            if i != j {
+                term_num = term_num.checked_mul(x as i128 - xj as i128)?;
+                term_den = term_den.checked_mul(xi as i128 - xj as i128)?;
+                if term_den == 0 {
+                    return None;
+                }
+                (term_num, term_den) = reduce(term_num, term_den);
+            }
+        }
+        num = num
+            .checked_mul(term_den)?
+            .checked_add(term_num.checked_mul(den)?)?;
+        den = den.checked_mul(term_den)?;
+        (num, den) = reduce(num, den);
+    }
+    (num % den == 0).then(|| num / den)
+}
+
+/// Divides out the greatest common divisor and makes the denominator positive
+fn reduce(num: i128, den: i128) -> (i128, i128) {
+    let (mut a, mut b) = (num.unsigned_abs(), den.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    let gcd = a.max(1) as i128 * den.signum();
+    (num / gcd, den / gcd)
+}
+
+#[test]
+fn polynomial_extrapolation() {
+    let p = Polynomial::fit(&[10, 13, 16, 21, 30, 45]).unwrap();
+    assert_eq!(p.degree(), 3);
+    assert!(p.is_determined());
+    assert_eq!(p.forward(1), Some(68));
+    assert_eq!(p.backward(1), Some(5));
+
+    let constant = Polynomial::fit(&[7, 7]).unwrap();
+    assert_eq!((constant.degree(), constant.eval(-1_000_000)), (0, Some(7)));
+    assert!(!Polynomial::fit(&[1, 2]).unwrap().is_determined());
+    assert_eq!(Polynomial::fit(&[]), None);
+    let extremes = [i64::MIN, i64::MAX].repeat(40);
+    assert_eq!(Polynomial::fit(&extremes), None);
+
+    // Squares, far out and past i64
+    let squares = Polynomial::fit(&[0, 1, 4, 9]).unwrap();
+    assert_eq!(squares.eval(26501365), Some(26501365i128 * 26501365));
+    assert_eq!(
+        squares.eval(i64::MAX),
+        Some(i64::MAX as i128 * i64::MAX as i128)
+    );
+    let cubes = Polynomial::fit(&[0, 1, 8, 27, 64]).unwrap();
+    assert_eq!(cubes.eval(i64::MAX), None);
+
+    assert_eq!(
+        interpolate(&[(65, 3), (196, 5), (327, 7)], 26501365),
+        Some(404603)
+    );
+    assert_eq!(interpolate(&[(0, 0), (2, 1)], 1), None);
+    assert_eq!(interpolate(&[(1, 1), (1, 2)], 0), None);
+}
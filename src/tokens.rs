@@ -0,0 +1,140 @@
+use crate::{adjacents, Co2, Grid};
+use itertools::Itertools;
+use std::{collections::HashMap, ops::Range};
+
+/// Horizontal run of cells in a grid with the value parsed from it
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Span<V> {
+    pub row: usize,
+    pub cols: Range<usize>,
+    pub value: V,
+}
+
+impl<V> Span<V> {
+    pub fn cells(&self) -> impl Iterator<Item = Co2<usize>> + '_ {
+        self.cols.clone().map(|col| Co2(self.row, col))
+    }
+
+    /// Returns the cells 8-adjacent to the span, excluding the span itself and cells outside of
+    /// a `rows` x `cols` grid
+    pub fn boundary(&self, rows: usize, cols: usize) -> Vec<Co2<usize>> {
+        self.cells()
+            .flat_map(|co| adjacents(co.as_tuple(), rows, cols))
+            .filter(|&(row, col)| row != self.row || !self.cols.contains(&col))
+            .unique()
+            .map(Co2::from)
+            .collect()
+    }
+}
+
+/// Extracts the maximal horizontal runs of cells matching `is_token` that `parse` accepts
+pub fn extract_spans<T, V>(
+    grid: &Grid<T>,
+    is_token: impl Fn(&T) -> bool,
+    parse: impl Fn(&[T]) -> Option<V>,
+) -> Vec<Span<V>> {
+    let mut spans = vec![];
+    for (row, line) in grid.iter_rows().enumerate() {
+        let mut col = 0;
+        while col < line.len() {
+            if !is_token(&line[col]) {
+                col += 1;
+                continue;
+            }
+            let start = col;
+            while col < line.len() && is_token(&line[col]) {
+                col += 1;
+            }
+            if let Some(value) = parse(&line[start..col]) {
+                spans.push(Span {
+                    row,
+                    cols: start..col,
+                    value,
+                });
+            }
+        }
+    }
+    spans
+}
+
+/// Extracts the numbers written left to right in `grid`
+pub fn extract_numbers(grid: &Grid<char>) -> Vec<Span<u64>> {
+    extract_spans(grid, char::is_ascii_digit, |digits| {
+        digits.iter().collect::<String>().parse().ok()
+    })
+}
+
+/// Which symbols each span touches and which spans touch each symbol
+///
+/// Spans are referred to by their index in the slice the index was built from.
+#[derive(Debug, Clone, Default)]
+pub struct AdjacencyIndex {
+    symbols_of: Vec<Vec<Co2<usize>>>,
+    spans_at: HashMap<Co2<usize>, Vec<usize>>,
+}
+
+impl AdjacencyIndex {
+    /// Indexes the cells matching `is_symbol` on the boundary of each of `spans`
+    pub fn new<T, V>(grid: &Grid<T>, spans: &[Span<V>], is_symbol: impl Fn(&T) -> bool) -> Self {
+        let mut spans_at: HashMap<_, Vec<_>> = HashMap::new();
+        let symbols_of = spans
+            .iter()
+            .enumerate()
+            .map(|(idx, span)| {
+                let symbols = span
+                    .boundary(grid.rows(), grid.cols())
+                    .into_iter()
+                    .filter(|&co| is_symbol(&grid[co]))
+                    .collect_vec();
+                for &co in &symbols {
+                    spans_at.entry(co).or_default().push(idx);
+                }
+                symbols
+            })
+            .collect();
+
+        AdjacencyIndex {
+            symbols_of,
+            spans_at,
+        }
+    }
+
+    /// Returns the symbols adjacent to the span at `span_idx`
+    pub fn symbols_of(&self, span_idx: usize) -> &[Co2<usize>] {
+        &self.symbols_of[span_idx]
+    }
+
+    /// Returns the indices of the spans adjacent to the symbol at `co`
+    pub fn spans_at(&self, co: Co2<usize>) -> &[usize] {
+        self.spans_at.get(&co).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Returns an iterator across every symbol touching at least one span, with the spans
+    pub fn symbols(&self) -> impl Iterator<Item = (Co2<usize>, &[usize])> {
+        self.spans_at
+            .iter()
+            .map(|(co, spans)| (*co, spans.as_slice()))
+    }
+}
+
+#[test]
+fn extract_part_numbers() {
+    let grid = Grid::parse(
+        "467..114..
+...*......
+..35..633.
+......#...",
+    );
+    let numbers = extract_numbers(&grid);
+    assert_eq!(
+        numbers.iter().map(|s| s.value).collect_vec(),
+        [467, 114, 35, 633]
+    );
+    assert_eq!(numbers[1].cols, 5..8);
+
+    let index = AdjacencyIndex::new(&grid, &numbers, |c| *c != '.' && !c.is_ascii_digit());
+    assert_eq!(index.symbols_of(0), [Co2(1, 3)]);
+    assert!(index.symbols_of(1).is_empty());
+    assert_eq!(index.spans_at(Co2(1, 3)), [0, 2]);
+    assert_eq!(index.spans_at(Co2(3, 6)), [3]);
+}
@@ -0,0 +1,244 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Disjoint sets of the elements `0..len`, merged by rank with path compression
+#[derive(Debug, Clone, Default)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    size: Vec<usize>,
+    count: usize,
+}
+
+impl UnionFind {
+    /// Creates `len` singleton sets
+    pub fn new(len: usize) -> Self {
+        UnionFind {
+            parent: (0..len).collect(),
+            rank: vec![0; len],
+            size: vec![1; len],
+            count: len,
+        }
+    }
+
+    /// Adds a new singleton set and returns its element
+    pub fn push(&mut self) -> usize {
+        let v = self.parent.len();
+        self.parent.push(v);
+        self.rank.push(0);
+        self.size.push(1);
+        self.count += 1;
+        v
+    }
+
+    /// Number of elements
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Number of disjoint sets
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the representative element of the set containing `v`
+    pub fn find(&mut self, mut v: usize) -> usize {
+        while self.parent[v] != v {
+            self.parent[v] = self.parent[self.parent[v]];
+            v = self.parent[v];
+        }
+        v
+    }
+
+    /// Merges the sets containing `a` and `b`. Returns false if they were already the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.rank[a] < self.rank[b] {
+            (a, b) = (b, a);
+        }
+        if self.rank[a] == self.rank[b] {
+            self.rank[a] += 1;
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.count -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Number of elements in the set containing `v`
+    pub fn size(&mut self, v: usize) -> usize {
+        let root = self.find(v);
+        self.size[root]
+    }
+
+    /// Returns the elements in the same set as `v`, in increasing order
+    pub fn members(&mut self, v: usize) -> Vec<usize> {
+        let root = self.find(v);
+        (0..self.len()).filter(|&u| self.find(u) == root).collect()
+    }
+
+    /// Returns every set, ordered by their smallest element, with the elements in increasing
+    /// order
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut index = HashMap::new();
+        let mut components = Vec::<Vec<usize>>::new();
+        for v in 0..self.len() {
+            let root = self.find(v);
+            let idx = *index.entry(root).or_insert_with(|| {
+                components.push(vec![]);
+                components.len() - 1
+            });
+            components[idx].push(v);
+        }
+        components
+    }
+
+    /// Returns the size of every set, ordered by their smallest element
+    pub fn sizes(&mut self) -> Vec<usize> {
+        self.components().iter().map(Vec::len).collect()
+    }
+}
+
+/// Disjoint sets of keys, such as coordinates or interned names, added as they are first seen
+#[derive(Debug, Clone)]
+pub struct UnionFindMap<K> {
+    ids: HashMap<K, usize>,
+    keys: Vec<K>,
+    sets: UnionFind,
+}
+
+impl<K> Default for UnionFindMap<K> {
+    fn default() -> Self {
+        UnionFindMap {
+            ids: HashMap::new(),
+            keys: vec![],
+            sets: UnionFind::default(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone> UnionFindMap<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `key` as a singleton set if it has not been seen before
+    pub fn insert(&mut self, key: &K) {
+        self.id(key);
+    }
+
+    fn id(&mut self, key: &K) -> usize {
+        if let Some(&id) = self.ids.get(key) {
+            return id;
+        }
+        let id = self.sets.push();
+        self.ids.insert(key.clone(), id);
+        self.keys.push(key.clone());
+        id
+    }
+
+    /// Number of keys
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Number of disjoint sets
+    pub fn count(&self) -> usize {
+        self.sets.count()
+    }
+
+    /// Returns the representative key of the set containing `key`, if it has been seen
+    pub fn find(&mut self, key: &K) -> Option<&K> {
+        let id = *self.ids.get(key)?;
+        let root = self.sets.find(id);
+        Some(&self.keys[root])
+    }
+
+    /// Merges the sets containing `a` and `b`, adding the keys first if needed. Returns false if
+    /// they were already the same set.
+    pub fn union(&mut self, a: &K, b: &K) -> bool {
+        let (a, b) = (self.id(a), self.id(b));
+        self.sets.union(a, b)
+    }
+
+    /// Returns true if both keys have been seen and are in the same set
+    pub fn connected(&mut self, a: &K, b: &K) -> bool {
+        match (self.ids.get(a), self.ids.get(b)) {
+            (Some(&a), Some(&b)) => self.sets.connected(a, b),
+            _ => false,
+        }
+    }
+
+    /// Number of keys in the set containing `key`, zero if it has not been seen
+    pub fn size(&mut self, key: &K) -> usize {
+        self.ids
+            .get(key)
+            .copied()
+            .map_or(0, |id| self.sets.size(id))
+    }
+
+    /// Returns the keys in the same set as `key`, in order of first appearance
+    pub fn members(&mut self, key: &K) -> Vec<&K> {
+        match self.ids.get(key) {
+            Some(&id) => self
+                .sets
+                .members(id)
+                .into_iter()
+                .map(|id| &self.keys[id])
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// Returns every set, ordered by the first appearance of their keys
+    pub fn components(&mut self) -> Vec<Vec<&K>> {
+        self.sets
+            .components()
+            .into_iter()
+            .map(|ids| ids.into_iter().map(|id| &self.keys[id]).collect())
+            .collect()
+    }
+
+    /// Returns the size of every set, ordered by the first appearance of their keys
+    pub fn sizes(&mut self) -> Vec<usize> {
+        self.sets.sizes()
+    }
+}
+
+#[test]
+fn union_find_components() {
+    let mut sets = UnionFind::new(6);
+    assert!(sets.union(0, 3));
+    assert!(sets.union(4, 3));
+    assert!(!sets.union(0, 4));
+    sets.union(1, 5);
+    assert_eq!(sets.count(), 3);
+    assert!(sets.connected(4, 0) && !sets.connected(1, 2));
+    assert_eq!(sets.size(3), 3);
+    assert_eq!(sets.components(), [vec![0, 3, 4], vec![1, 5], vec![2]]);
+
+    let mut names = UnionFindMap::new();
+    names.union(&"jqt", &"rhn");
+    names.union(&"xhk", &"hfx");
+    names.union(&"rhn", &"xhk");
+    names.insert(&"cmg");
+    assert_eq!(names.count(), 2);
+    assert_eq!(names.members(&"hfx"), [&"jqt", &"rhn", &"xhk", &"hfx"]);
+    assert_eq!(names.sizes(), [4, 1]);
+    assert!(!names.connected(&"jqt", &"cmg"));
+    assert_eq!(names.find(&"nvd"), None);
+}